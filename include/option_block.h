@@ -0,0 +1,35 @@
+#ifndef OPTION_BLOCK_H
+#define OPTION_BLOCK_H
+
+/* C API for `option-block`. Requires building the crate with the `capi` feature. */
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum ObStatus {
+  OB_STATUS_OK = 0,
+  OB_STATUS_VACANT = 1,
+  OB_STATUS_REPLACED = 2,
+  OB_STATUS_NULL_HANDLE = -1,
+  OB_STATUS_OUT_OF_RANGE = -2,
+} ObStatus;
+
+/* Opaque handle to a heap-allocated `Block32<u32>`. */
+typedef struct Ob32U32 Ob32U32;
+
+Ob32U32 *ob32_u32_new(void);
+void ob32_u32_free(Ob32U32 *handle);
+ObStatus ob32_u32_insert(Ob32U32 *handle, uint32_t index, uint32_t value, uint32_t *old);
+ObStatus ob32_u32_get(const Ob32U32 *handle, uint32_t index, uint32_t *out);
+ObStatus ob32_u32_remove(Ob32U32 *handle, uint32_t index, uint32_t *out);
+ObStatus ob32_u32_mask(const Ob32U32 *handle, uint32_t *out);
+ObStatus ob32_u32_len(const Ob32U32 *handle, uint32_t *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OPTION_BLOCK_H */
@@ -0,0 +1,291 @@
+//! A block whose capacity is only known at runtime. See [`AnyBlock`](AnyBlock).
+
+use crate::{iter, Block128, Block16, Block32, Block64, Block8};
+
+/// A block of optionals whose capacity is chosen at runtime. Internally, this is simply
+/// an `enum` over the five fixed-size variants. All operations dispatch to the wrapped
+/// variant, so a small capacity does not pay for the storage of a [`Block128`](Block128).
+///
+/// # Example
+///
+/// ```rust
+/// use option_block::any::AnyBlock;
+///
+/// let mut block = AnyBlock::with_capacity_at_least(10).unwrap();
+/// assert_eq!(block.capacity(), 16);
+/// assert!(block.insert(9, 'a').is_none());
+///
+/// assert!(block.grow_to(40));
+/// assert_eq!(block.capacity(), 64);
+/// assert_eq!(block.get(9), Some(&'a'));
+/// ```
+#[derive(Debug, Clone)]
+pub enum AnyBlock<T> {
+    Block8(Block8<T>),
+    Block16(Block16<T>),
+    Block32(Block32<T>),
+    Block64(Block64<T>),
+    Block128(Block128<T>),
+}
+
+/// Forwards a method call to whichever variant is currently wrapped.
+macro_rules! dispatch {
+    ($any:expr, $block:ident => $body:expr) => {
+        match $any {
+            AnyBlock::Block8($block) => $body,
+            AnyBlock::Block16($block) => $body,
+            AnyBlock::Block32($block) => $body,
+            AnyBlock::Block64($block) => $body,
+            AnyBlock::Block128($block) => $body,
+        }
+    };
+}
+
+/// Moves every occupied value of a block into the same slot of a wider block.
+macro_rules! widen {
+    ($src:expr, $capacity:expr => $dst:ident) => {{
+        let mut src = $src;
+        let mut dst = $dst::default();
+        for idx in 0..$capacity {
+            if let Some(val) = src.remove(idx) {
+                dst.insert(idx, val);
+            }
+        }
+        AnyBlock::$dst(dst)
+    }};
+}
+
+macro_rules! impl_from_block {
+    ($($name:ident)*) => {$(
+        impl<T> From<$name<T>> for AnyBlock<T> {
+            fn from(block: $name<T>) -> Self {
+                Self::$name(block)
+            }
+        }
+    )*};
+}
+
+impl_from_block!(Block8 Block16 Block32 Block64 Block128);
+
+impl<T> Default for AnyBlock<T> {
+    fn default() -> Self {
+        Self::Block8(Block8::default())
+    }
+}
+
+impl<T> IntoIterator for AnyBlock<T> {
+    type Item = T;
+    type IntoIter = iter::AnyBlockIntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Self::Block8(block) => Self::IntoIter::Block8(block.into_iter()),
+            Self::Block16(block) => Self::IntoIter::Block16(block.into_iter()),
+            Self::Block32(block) => Self::IntoIter::Block32(block.into_iter()),
+            Self::Block64(block) => Self::IntoIter::Block64(block.into_iter()),
+            Self::Block128(block) => Self::IntoIter::Block128(block.into_iter()),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a AnyBlock<T> {
+    type Item = &'a T;
+    type IntoIter = iter::AnyBlockIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> AnyBlock<T> {
+    /// Maximum capacity among all the block variants.
+    pub const MAX_CAPACITY: usize = Block128::<T>::CAPACITY as usize;
+
+    /// Creates an empty block using the smallest variant that holds at least `capacity`
+    /// elements. Returns `None` if `capacity` exceeds the [maximum](Self::MAX_CAPACITY).
+    pub fn with_capacity_at_least(capacity: usize) -> Option<Self> {
+        Some(match capacity {
+            0..=8 => Self::Block8(Block8::default()),
+            9..=16 => Self::Block16(Block16::default()),
+            17..=32 => Self::Block32(Block32::default()),
+            33..=64 => Self::Block64(Block64::default()),
+            65..=128 => Self::Block128(Block128::default()),
+            _ => return None,
+        })
+    }
+
+    /// Returns the capacity of the currently wrapped variant.
+    pub const fn capacity(&self) -> usize {
+        match self {
+            Self::Block8(_) => Block8::<T>::CAPACITY as usize,
+            Self::Block16(_) => Block16::<T>::CAPACITY as usize,
+            Self::Block32(_) => Block32::<T>::CAPACITY as usize,
+            Self::Block64(_) => Block64::<T>::CAPACITY as usize,
+            Self::Block128(_) => Block128::<T>::CAPACITY as usize,
+        }
+    }
+
+    /// Widens the block in place (moving all values) so that it holds at least `capacity`
+    /// elements. This is a no-op if the current capacity is already sufficient. Returns
+    /// `false` (leaving the block untouched) if `capacity` exceeds the [maximum](Self::MAX_CAPACITY).
+    pub fn grow_to(&mut self, capacity: usize) -> bool {
+        if capacity <= self.capacity() {
+            return true;
+        }
+
+        let Some(target) = Self::with_capacity_at_least(capacity) else {
+            return false;
+        };
+
+        let current = self.capacity();
+        let block = core::mem::take(self);
+        *self = match target {
+            Self::Block8(_) => unreachable!("a non-empty widening never targets the smallest variant"),
+            Self::Block16(_) => dispatch!(block, src => widen!(src, current => Block16)),
+            Self::Block32(_) => dispatch!(block, src => widen!(src, current => Block32)),
+            Self::Block64(_) => dispatch!(block, src => widen!(src, current => Block64)),
+            Self::Block128(_) => dispatch!(block, src => widen!(src, current => Block128)),
+        };
+
+        true
+    }
+
+    /// Checks whether the item at the `index` is vacant (i.e. contains `None`).
+    ///
+    /// # Panic
+    /// Panics if `index >= capacity()`.
+    pub fn is_vacant(&self, index: usize) -> bool {
+        dispatch!(self, block => block.is_vacant(index))
+    }
+
+    /// Returns the number of non-null elements in the block.
+    pub fn len(&self) -> u32 {
+        dispatch!(self, block => block.len())
+    }
+
+    /// Returns `true` if the block contains zero elements.
+    pub fn is_empty(&self) -> bool {
+        dispatch!(self, block => block.is_empty())
+    }
+
+    /// Attempts to retrieve a shared reference to the element at `index`.
+    /// Returns `None` if the slot is vacant (i.e. uninitialized).
+    ///
+    /// # Panic
+    /// Panics if `index >= capacity()`.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        dispatch!(self, block => block.get(index))
+    }
+
+    /// Attempts to retrieve an exclusive reference to the element at
+    /// `index`. Returns `None` if the slot is vacant (i.e. uninitialized).
+    ///
+    /// # Panic
+    /// Panics if `index >= capacity()`.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        dispatch!(self, block => block.get_mut(index))
+    }
+
+    /// See the [`get_or_else`](Block8::get_or_else) method of the fixed-size variants.
+    pub fn get_or_else(&mut self, index: usize, func: impl FnOnce() -> T) -> &mut T {
+        dispatch!(self, block => block.get_or_else(index, func))
+    }
+
+    /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
+    pub fn get_or(&mut self, index: usize, val: T) -> &mut T {
+        self.get_or_else(index, || val)
+    }
+
+    /// Inserts the `val` at the `index`. If a value already exists, it returns `Some`
+    /// containing the old value. Otherwise, it returns `None`.
+    ///
+    /// # Panic
+    /// Panics if `index >= capacity()`.
+    pub fn insert(&mut self, index: usize, val: T) -> Option<T> {
+        dispatch!(self, block => block.insert(index, val))
+    }
+
+    /// Removes the value at the `index`. If a value already exists, it returns `Some`
+    /// containing that value. Otherwise, it returns `None`.
+    ///
+    /// # Panic
+    /// Panics if `index >= capacity()`.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        dispatch!(self, block => block.remove(index))
+    }
+
+    /// Create a by-reference iterator for this block.
+    pub fn iter(&self) -> iter::AnyBlockIter<'_, T> {
+        match self {
+            Self::Block8(block) => iter::AnyBlockIter::Block8(block.iter()),
+            Self::Block16(block) => iter::AnyBlockIter::Block16(block.iter()),
+            Self::Block32(block) => iter::AnyBlockIter::Block32(block.iter()),
+            Self::Block64(block) => iter::AnyBlockIter::Block64(block.iter()),
+            Self::Block128(block) => iter::AnyBlockIter::Block128(block.iter()),
+        }
+    }
+}
+
+impl<T: Default> AnyBlock<T> {
+    /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
+    pub fn get_or_default(&mut self, index: usize) -> &mut T {
+        self.get_or_else(index, Default::default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capacity_thresholds() {
+        for (requested, expected) in [(0, 8), (8, 8), (9, 16), (16, 16), (17, 32), (33, 64), (64, 64), (65, 128)] {
+            let block = AnyBlock::<()>::with_capacity_at_least(requested).unwrap();
+            assert_eq!(block.capacity(), expected);
+        }
+        assert!(AnyBlock::<()>::with_capacity_at_least(129).is_none());
+    }
+
+    #[test]
+    fn growth_preserves_contents() {
+        let mut block = AnyBlock::default();
+        assert!(block.insert(0, 10).is_none());
+        assert!(block.insert(7, 70).is_none());
+
+        assert!(block.grow_to(8));
+        assert_eq!(block.capacity(), 8);
+
+        for (target, expected) in [(16, 16), (20, 32), (64, 64), (100, 128)] {
+            assert!(block.grow_to(target));
+            assert_eq!(block.capacity(), expected);
+            assert_eq!(block.len(), 2);
+            assert_eq!(block.get(0), Some(&10));
+            assert_eq!(block.get(7), Some(&70));
+        }
+
+        assert!(block.insert(127, 1270).is_none());
+        assert!(!block.grow_to(129));
+        assert!(block.grow_to(16));
+        assert_eq!(block.capacity(), 128);
+        assert_eq!(block.len(), 3);
+    }
+
+    #[test]
+    fn parity_with_concrete_types() {
+        let pairs = [(1, 'a'), (5, 'b'), (6, 'c')];
+        let concrete: Block8<_> = pairs.into_iter().collect();
+        let mut any = AnyBlock::from(concrete.clone());
+        assert!(any.iter().eq(concrete.iter()));
+
+        for width in [16, 32, 64, 128] {
+            assert!(any.grow_to(width));
+            assert!(any.iter().eq(concrete.iter()));
+            assert!(any.clone().into_iter().eq(concrete.clone().into_iter()));
+            assert_eq!(any.iter().len(), concrete.iter().len());
+        }
+
+        assert_eq!(any.remove(5), Some('b'));
+        assert_eq!(any.insert(100, 'z'), None);
+        assert_eq!(*any.get_or(1, 'x'), 'a');
+        assert!(any.is_vacant(5));
+        assert!(any.iter().copied().eq(['a', 'c', 'z']));
+    }
+}
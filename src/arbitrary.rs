@@ -0,0 +1,81 @@
+//! [`quickcheck::Arbitrary`] implementations for the block variants. A random mask is
+//! generated first, and then a random value is generated for each occupied slot. Shrinking
+//! first vacates the occupied slots one at a time and then shrinks the values in place.
+
+extern crate alloc;
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use alloc::boxed::Box;
+use quickcheck::{Arbitrary, Gen};
+
+macro_rules! impl_arbitrary {
+    ($($name:ident $int:ty)*) => {$(
+        impl<T: Arbitrary> Arbitrary for $name<T> {
+            fn arbitrary(g: &mut Gen) -> Self {
+                let mut block = Self::default();
+                let mut mask = <$int>::arbitrary(g);
+                while mask != 0 {
+                    let idx = mask.trailing_zeros() as usize;
+                    mask &= mask - 1;
+                    block.insert(idx, T::arbitrary(g));
+                }
+                block
+            }
+
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                let block = self.clone();
+                let vacated = self.occupied_indices().map(move |idx| {
+                    let mut smaller = block.clone();
+                    smaller.remove(idx);
+                    smaller
+                });
+
+                let block = self.clone();
+                let shrunk = self.occupied_indices().flat_map(move |idx| {
+                    let block = block.clone();
+                    block[idx].shrink().map(move |val| {
+                        let mut smaller = block.clone();
+                        smaller.insert(idx, val);
+                        smaller
+                    })
+                });
+
+                Box::new(vacated.chain(shrunk))
+            }
+        }
+    )*};
+}
+
+impl_arbitrary!(Block8 u8 Block16 u16 Block32 u32 Block64 u64 Block128 u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    fn insert_then_remove(mut block: Block64<u32>, index: u8, val: u32) -> bool {
+        let index = usize::from(index) % 64;
+        let old = block.get(index).copied();
+        block.insert(index, val) == old && block.remove(index) == Some(val) && block.is_vacant(index)
+    }
+
+    fn len_is_popcount(block: Block128<u8>) -> bool {
+        block.len() == block.occupied_indices().count() as u32 && block.iter().count() == block.len() as usize
+    }
+
+    #[test]
+    fn properties() {
+        QuickCheck::new().quickcheck(insert_then_remove as fn(Block64<u32>, u8, u32) -> bool);
+        QuickCheck::new().quickcheck(len_is_popcount as fn(Block128<u8>) -> bool);
+    }
+
+    #[test]
+    fn shrinking_vacates_then_shrinks() {
+        let block: Block8<u32> = [(1, 5), (4, 0)].into_iter().collect();
+        let mut shrunk = block.shrink();
+        assert_eq!(shrunk.next(), Some([(4, 0)].into_iter().collect()));
+        assert_eq!(shrunk.next(), Some([(1, 5)].into_iter().collect()));
+        assert!(shrunk.all(|smaller| smaller.occupied_indices().eq([1, 4]) && smaller[1] < 5 && smaller[4] == 0));
+        assert!(Block8::<u32>::default().shrink().next().is_none());
+    }
+}
@@ -0,0 +1,62 @@
+//! Native [`bincode`] (version 2) implementations for the block variants, which do not go
+//! through `serde`. A block is encoded as its mask followed by the occupied values only
+//! (in ascending index order). Decoding expects exactly as many values as there are set bits
+//! in the mask; running out of input is a decode error rather than a half-initialized block.
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use bincode::{
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    BorrowDecode, Decode, Encode,
+};
+
+macro_rules! impl_bincode {
+    ($($name:ident $int:ty)*) => {$(
+        impl<T: Encode> Encode for $name<T> {
+            fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+                self.mask.encode(encoder)?;
+                for val in self {
+                    val.encode(encoder)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl<Context, T: Decode<Context>> Decode<Context> for $name<T> {
+            fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+                let mut mask = <$int>::decode(decoder)?;
+                decoder.claim_container_read::<T>(mask.count_ones() as usize)?;
+
+                // Should a later value fail, the partially filled block is dropped along
+                // with the error. Hence, no decoded value is leaked.
+                let mut block = Self::default();
+                while mask != 0 {
+                    let idx = mask.trailing_zeros() as usize;
+                    mask &= mask - 1;
+                    decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+                    block.insert(idx, T::decode(decoder)?);
+                }
+                Ok(block)
+            }
+        }
+
+        impl<'de, Context, T: BorrowDecode<'de, Context>> BorrowDecode<'de, Context> for $name<T> {
+            fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+                let mut mask = <$int>::decode(decoder)?;
+                decoder.claim_container_read::<T>(mask.count_ones() as usize)?;
+
+                let mut block = Self::default();
+                while mask != 0 {
+                    let idx = mask.trailing_zeros() as usize;
+                    mask &= mask - 1;
+                    decoder.unclaim_bytes_read(core::mem::size_of::<T>());
+                    block.insert(idx, T::borrow_decode(decoder)?);
+                }
+                Ok(block)
+            }
+        }
+    )*};
+}
+
+impl_bincode!(Block8 u8 Block16 u16 Block32 u32 Block64 u64 Block128 u128);
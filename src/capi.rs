@@ -0,0 +1,229 @@
+//! A minimal C API for sharing a [`Block32<u32>`](Block32) with C code. This module is
+//! only available with the `capi` feature, which also requires a global allocator since
+//! the block is allocated on the heap behind an opaque handle.
+//!
+//! No function in this module ever panics across the FFI boundary. Null handles and
+//! out-of-range indices are reported as [status codes](ObStatus) instead. A matching
+//! header is available in `include/option_block.h`.
+
+extern crate alloc;
+
+use crate::Block32;
+use alloc::boxed::Box;
+
+/// Opaque handle to a heap-allocated [`Block32<u32>`](Block32).
+pub type Ob32U32 = Block32<u32>;
+
+/// Status codes returned by the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObStatus {
+    /// The operation succeeded. For `get` and `remove`, the slot was occupied.
+    /// For `insert`, the slot was previously vacant.
+    Ok = 0,
+    /// The slot was vacant (only returned by `get` and `remove`).
+    Vacant = 1,
+    /// The slot was previously occupied and its old value has been written
+    /// out (only returned by `insert`).
+    Replaced = 2,
+    /// The given handle was null.
+    NullHandle = -1,
+    /// The given index was out of range.
+    OutOfRange = -2,
+}
+
+/// Validates the index against the capacity of the block.
+fn check_index(index: u32) -> Result<usize, ObStatus> {
+    if index < Ob32U32::CAPACITY {
+        Ok(index as usize)
+    } else {
+        Err(ObStatus::OutOfRange)
+    }
+}
+
+/// Writes the `val` into the `out` pointer (if non-null).
+///
+/// # Safety
+/// The `out` pointer must either be null or valid for writes.
+unsafe fn write_out(out: *mut u32, val: u32) {
+    if let Some(out) = out.as_mut() {
+        *out = val;
+    }
+}
+
+/// Allocates a new empty block. The handle must be released with [`ob32_u32_free`].
+#[no_mangle]
+pub extern "C" fn ob32_u32_new() -> *mut Ob32U32 {
+    Box::into_raw(Box::default())
+}
+
+/// Releases a block previously allocated by [`ob32_u32_new`]. Null handles are ignored.
+///
+/// # Safety
+/// The `handle` must either be null or a live handle returned by [`ob32_u32_new`].
+/// The handle must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn ob32_u32_free(handle: *mut Ob32U32) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Inserts the `value` at the `index`. If the slot was previously occupied, the old value
+/// is written into `old` (if non-null) and [`ObStatus::Replaced`] is returned.
+///
+/// # Safety
+/// The `handle` must either be null or a live handle. The `old` pointer must either be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ob32_u32_insert(handle: *mut Ob32U32, index: u32, value: u32, old: *mut u32) -> ObStatus {
+    let Some(block) = handle.as_mut() else {
+        return ObStatus::NullHandle;
+    };
+
+    let index = match check_index(index) {
+        Ok(index) => index,
+        Err(status) => return status,
+    };
+
+    match block.insert(index, value) {
+        Some(prev) => {
+            write_out(old, prev);
+            ObStatus::Replaced
+        }
+        None => ObStatus::Ok,
+    }
+}
+
+/// Reads the value at the `index` into `out` (if non-null).
+///
+/// # Safety
+/// The `handle` must either be null or a live handle. The `out` pointer must either be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ob32_u32_get(handle: *const Ob32U32, index: u32, out: *mut u32) -> ObStatus {
+    let Some(block) = handle.as_ref() else {
+        return ObStatus::NullHandle;
+    };
+
+    let index = match check_index(index) {
+        Ok(index) => index,
+        Err(status) => return status,
+    };
+
+    match block.get(index) {
+        Some(&val) => {
+            write_out(out, val);
+            ObStatus::Ok
+        }
+        None => ObStatus::Vacant,
+    }
+}
+
+/// Removes the value at the `index`, writing it into `out` (if non-null).
+///
+/// # Safety
+/// The `handle` must either be null or a live handle. The `out` pointer must either be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ob32_u32_remove(handle: *mut Ob32U32, index: u32, out: *mut u32) -> ObStatus {
+    let Some(block) = handle.as_mut() else {
+        return ObStatus::NullHandle;
+    };
+
+    let index = match check_index(index) {
+        Ok(index) => index,
+        Err(status) => return status,
+    };
+
+    match block.remove(index) {
+        Some(val) => {
+            write_out(out, val);
+            ObStatus::Ok
+        }
+        None => ObStatus::Vacant,
+    }
+}
+
+/// Writes the occupancy mask into `out` (if non-null). Bit `i` is set iff slot `i` is occupied.
+///
+/// # Safety
+/// The `handle` must either be null or a live handle. The `out` pointer must either be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ob32_u32_mask(handle: *const Ob32U32, out: *mut u32) -> ObStatus {
+    let Some(block) = handle.as_ref() else {
+        return ObStatus::NullHandle;
+    };
+
+    write_out(out, block.mask);
+    ObStatus::Ok
+}
+
+/// Writes the number of occupied slots into `out` (if non-null).
+///
+/// # Safety
+/// The `handle` must either be null or a live handle. The `out` pointer must either be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ob32_u32_len(handle: *const Ob32U32, out: *mut u32) -> ObStatus {
+    let Some(block) = handle.as_ref() else {
+        return ObStatus::NullHandle;
+    };
+
+    write_out(out, block.len());
+    ObStatus::Ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr::{null, null_mut};
+
+    #[test]
+    fn round_trip() {
+        let handle = ob32_u32_new();
+        let mut out = 0;
+        unsafe {
+            assert_eq!(ob32_u32_insert(handle, 3, 30, null_mut()), ObStatus::Ok);
+            assert_eq!(ob32_u32_insert(handle, 31, 310, null_mut()), ObStatus::Ok);
+            assert_eq!(ob32_u32_insert(handle, 3, 33, &mut out), ObStatus::Replaced);
+            assert_eq!(out, 30);
+
+            assert_eq!(ob32_u32_len(handle, &mut out), ObStatus::Ok);
+            assert_eq!(out, 2);
+            assert_eq!(ob32_u32_mask(handle, &mut out), ObStatus::Ok);
+            assert_eq!(out, 1 << 3 | 1 << 31);
+
+            assert_eq!(ob32_u32_get(handle, 3, &mut out), ObStatus::Ok);
+            assert_eq!(out, 33);
+            assert_eq!(ob32_u32_get(handle, 4, &mut out), ObStatus::Vacant);
+            assert_eq!(ob32_u32_remove(handle, 31, &mut out), ObStatus::Ok);
+            assert_eq!(out, 310);
+            assert_eq!(ob32_u32_remove(handle, 31, &mut out), ObStatus::Vacant);
+
+            ob32_u32_free(handle);
+        }
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        let mut out = 0;
+        unsafe {
+            assert_eq!(ob32_u32_insert(null_mut(), 0, 0, null_mut()), ObStatus::NullHandle);
+            assert_eq!(ob32_u32_get(null(), 0, &mut out), ObStatus::NullHandle);
+            assert_eq!(ob32_u32_remove(null_mut(), 0, &mut out), ObStatus::NullHandle);
+            assert_eq!(ob32_u32_mask(null(), &mut out), ObStatus::NullHandle);
+            assert_eq!(ob32_u32_len(null(), &mut out), ObStatus::NullHandle);
+            ob32_u32_free(null_mut());
+
+            let handle = ob32_u32_new();
+            assert_eq!(ob32_u32_insert(handle, 32, 0, null_mut()), ObStatus::OutOfRange);
+            assert_eq!(ob32_u32_get(handle, 32, &mut out), ObStatus::OutOfRange);
+            assert_eq!(ob32_u32_remove(handle, u32::MAX, &mut out), ObStatus::OutOfRange);
+            assert_eq!(ob32_u32_len(handle, &mut out), ObStatus::Ok);
+            assert_eq!(out, 0);
+            ob32_u32_free(handle);
+        }
+    }
+}
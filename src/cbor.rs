@@ -0,0 +1,101 @@
+//! [`minicbor`] implementations for the block variants. A block is encoded as a CBOR map
+//! from slot index to value, so sparse blocks stay small on the wire.
+//!
+//! Decoding accepts both definite and indefinite-length maps. Indices that are out of range
+//! (i.e. `>= CAPACITY`) are rejected with a decode error. Duplicate indices are rejected as
+//! well (rather than letting the last one win), since a well-formed encoder never emits them.
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use minicbor::{
+    decode::{self, Decoder},
+    encode::{self, Encoder, Write},
+    Decode, Encode,
+};
+
+macro_rules! impl_cbor {
+    ($($name:ident)*) => {$(
+        impl<C, T: Encode<C>> Encode<C> for $name<T> {
+            fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+                e.map(u64::from(self.len()))?;
+                for (idx, val) in self.iter_entries() {
+                    e.u8(idx as u8)?;
+                    val.encode(e, ctx)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl<'b, C, T: Decode<'b, C>> Decode<'b, C> for $name<T> {
+            fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+                // Should a later entry fail, the partially filled block is dropped along
+                // with the error. Hence, no decoded value is leaked.
+                let mut block = Self::default();
+                for entry in d.map_iter_with::<C, u32, T>(ctx)? {
+                    let (idx, val) = entry?;
+                    let idx = idx as usize;
+                    if idx >= Self::CAPACITY as usize {
+                        return Err(decode::Error::message("slot index out of range"));
+                    }
+                    if block.insert(idx, val).is_some() {
+                        return Err(decode::Error::message("duplicate slot index"));
+                    }
+                }
+                Ok(block)
+            }
+        }
+    )*};
+}
+
+impl_cbor!(Block8 Block16 Block32 Block64 Block128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minicbor::encode::write::Cursor;
+
+    fn encode<'b, T: Encode<()>>(val: &T, buf: &'b mut [u8]) -> &'b [u8] {
+        let mut encoder = Encoder::new(Cursor::new(buf));
+        encoder.encode(val).unwrap();
+        let len = encoder.writer().position();
+        &encoder.into_writer().into_inner()[..len]
+    }
+
+    #[test]
+    fn round_trips() {
+        let mut buf = [0; 512];
+
+        let empty = Block128::<u32>::default();
+        let bytes = encode(&empty, &mut buf);
+        assert_eq!(bytes, [0xa0]);
+        assert_eq!(minicbor::decode::<Block128<u32>>(bytes).unwrap(), empty);
+
+        let sparse: Block16<u32> = [(0, 1), (15, 1000)].into_iter().collect();
+        let bytes = encode(&sparse, &mut buf);
+        assert_eq!(bytes, [0xa2, 0x00, 0x01, 0x0f, 0x19, 0x03, 0xe8]);
+        assert_eq!(minicbor::decode::<Block16<u32>>(bytes).unwrap(), sparse);
+
+        let full = Block64::from([7u8; 64]);
+        let bytes = encode(&full, &mut buf);
+        assert_eq!(minicbor::decode::<Block64<u8>>(bytes).unwrap(), full);
+
+        let words: Block8<&str> = [(3, "three")].into_iter().collect();
+        let bytes = encode(&words, &mut buf);
+        assert_eq!(minicbor::decode::<Block8<&str>>(bytes).unwrap(), words);
+
+        // Indefinite-length maps are accepted as well.
+        let indefinite = [0xbf, 0x02, 0x05, 0x18, 0x1f, 0x06, 0xff];
+        let block = minicbor::decode::<Block32<u8>>(&indefinite).unwrap();
+        assert_eq!(block, [(2, 5), (31, 6)].into_iter().collect());
+    }
+
+    #[test]
+    fn invalid_indices_are_rejected() {
+        // {8: 1} is out of range for a `Block8`.
+        assert!(minicbor::decode::<Block8<u8>>(&[0xa1, 0x08, 0x01]).is_err());
+        // {2: 1, 2: 3} contains a duplicate index.
+        assert!(minicbor::decode::<Block8<u8>>(&[0xa2, 0x02, 0x01, 0x02, 0x03]).is_err());
+        // A truncated map is an error rather than a partial block.
+        assert!(minicbor::decode::<Block8<u8>>(&[0xa2, 0x02, 0x01]).is_err());
+        assert!(minicbor::decode::<Block16<u8>>(&[0xa1, 0x0f, 0x01]).is_ok());
+    }
+}
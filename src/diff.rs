@@ -0,0 +1,95 @@
+//! Change records for replicating the contents of one block onto another.
+//! The `diff` method of each block variant produces a stream of borrowed
+//! [`Change`](Change) records, which may be converted into [`ChangeOwned`](ChangeOwned)
+//! records and later replayed onto another block via the `apply_diff` method.
+//!
+//! # Example
+//!
+//! ```rust
+//! use option_block::{diff::Change, Block8};
+//!
+//! let old: Block8<_> = [(0, 'a'), (1, 'b')].into_iter().collect();
+//! let new: Block8<_> = [(1, 'c'), (2, 'd')].into_iter().collect();
+//!
+//! let mut copy = old.clone();
+//! copy.apply_diff(old.diff(&new).map(Change::to_owned)).unwrap();
+//! assert_eq!(copy.get(0), None);
+//! assert_eq!(copy.get(1), Some(&'c'));
+//! assert_eq!(copy.get(2), Some(&'d'));
+//! ```
+
+use core::fmt;
+
+/// A borrowed change record describing how a single slot differs between two blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change<'a, T> {
+    /// The slot was vacant and is now occupied by the given value.
+    Added(usize, &'a T),
+    /// The slot was occupied and is now vacant.
+    Removed(usize),
+    /// The slot was occupied and now holds a different value.
+    Modified(usize, &'a T),
+}
+
+impl<T> Change<'_, T> {
+    /// Returns the index of the slot affected by this change.
+    pub const fn index(&self) -> usize {
+        match *self {
+            Self::Added(idx, _) | Self::Removed(idx) | Self::Modified(idx, _) => idx,
+        }
+    }
+}
+
+impl<T: Clone> Change<'_, T> {
+    /// Clones the referenced value (if any) into an owned change record.
+    pub fn to_owned(self) -> ChangeOwned<T> {
+        match self {
+            Self::Added(idx, val) => ChangeOwned::Added(idx, val.clone()),
+            Self::Removed(idx) => ChangeOwned::Removed(idx),
+            Self::Modified(idx, val) => ChangeOwned::Modified(idx, val.clone()),
+        }
+    }
+}
+
+/// An owned change record which may be replayed onto a block via `apply_diff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeOwned<T> {
+    /// Inserts the value into a slot that **must** be vacant.
+    Added(usize, T),
+    /// Removes the value from a slot that **must** be occupied.
+    Removed(usize),
+    /// Replaces the value of a slot that **must** be occupied.
+    Modified(usize, T),
+}
+
+impl<T> ChangeOwned<T> {
+    /// Returns the index of the slot affected by this change.
+    pub const fn index(&self) -> usize {
+        match *self {
+            Self::Added(idx, _) | Self::Removed(idx) | Self::Modified(idx, _) => idx,
+        }
+    }
+}
+
+/// Error returned by `apply_diff` when a change does not match the current state of the block.
+/// That is, an [`Added`](ChangeOwned::Added) change targets an occupied slot, a
+/// [`Removed`](ChangeOwned::Removed) or [`Modified`](ChangeOwned::Modified) change targets a
+/// vacant slot, or the index is out of bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffMismatch<T> {
+    /// Number of changes that were successfully applied before the mismatch.
+    pub applied: usize,
+    /// The offending change, which has **not** been applied.
+    pub change: ChangeOwned<T>,
+}
+
+impl<T> fmt::Display for DiffMismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.change {
+            ChangeOwned::Added(..) => "addition to",
+            ChangeOwned::Removed(..) => "removal from",
+            ChangeOwned::Modified(..) => "modification of",
+        };
+        write!(f, "change #{} is an invalid {kind} slot {}", self.applied, self.change.index())
+    }
+}
@@ -0,0 +1,981 @@
+//! Blocks with an exact (const generic) capacity. See [`Block`](Block) for more details.
+
+use crate::{diff, iter, occupancy, Block128, Block16, Block32, Block64, Block8, FixedBlock, OccupiedError};
+use core::{
+    mem::{size_of, ManuallyDrop, MaybeUninit},
+    ops::{Index, IndexMut},
+};
+
+pub(crate) mod sealed {
+    /// Integer types which may serve as the occupancy mask of a [`Block`](super::Block).
+    /// All operations are performed over a widened `u128` for simplicity.
+    pub trait Mask: Copy + core::fmt::Debug {
+        const ZERO: Self;
+        fn to_bits(self) -> u128;
+        fn from_bits(bits: u128) -> Self;
+    }
+}
+
+use sealed::Mask;
+
+macro_rules! impl_mask {
+    ($($int:ty)*) => {$(
+        impl Mask for $int {
+            const ZERO: Self = 0;
+
+            fn to_bits(self) -> u128 {
+                self.into()
+            }
+
+            fn from_bits(bits: u128) -> Self {
+                bits as Self
+            }
+        }
+    )*};
+}
+
+impl_mask!(u8 u16 u32 u64 u128);
+
+/// Type-level marker for the capacity `N` of a [`Block`](Block).
+pub struct Capacity<const N: usize>;
+
+/// Implemented for every [`Capacity`](Capacity) that a [`Block`](Block) supports (i.e. `1..=128`).
+/// The associated mask is the smallest integer type with at least `N` bits.
+pub trait SupportedCapacity {
+    /// The integer type used as the occupancy mask.
+    type Mask: Mask;
+}
+
+macro_rules! impl_supported_capacity {
+    ($int:ty: $($n:literal)*) => {$(
+        impl SupportedCapacity for Capacity<$n> {
+            type Mask = $int;
+        }
+    )*};
+}
+
+impl_supported_capacity!(u8: 1 2 3 4 5 6 7 8);
+impl_supported_capacity!(u16: 9 10 11 12 13 14 15 16);
+impl_supported_capacity!(u32: 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31 32);
+impl_supported_capacity!(u64:
+    33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48
+    49 50 51 52 53 54 55 56 57 58 59 60 61 62 63 64
+);
+impl_supported_capacity!(u128:
+    65 66 67 68 69 70 71 72 73 74 75 76 77 78 79 80
+    81 82 83 84 85 86 87 88 89 90 91 92 93 94 95 96
+    97 98 99 100 101 102 103 104 105 106 107 108 109 110 111 112
+    113 114 115 116 117 118 119 120 121 122 123 124 125 126 127 128
+);
+
+type MaskOf<const N: usize> = <Capacity<N> as SupportedCapacity>::Mask;
+
+/// A fixed block of exactly `N` optionals (where `1 <= N <= 128`), masked by the smallest
+/// integer type with at least `N` bits. Unlike the [`Block8`](Block8)..[`Block128`](Block128)
+/// variants, the storage holds exactly `N` elements, so no memory is wasted when the required
+/// capacity is not a power of two. The [`TryFrom`] conversions move the entries to and from
+/// the fixed-size variants.
+///
+/// This type provides the core accessors, mutators, and iterators of the fixed-size variants
+/// (along with the [`FixedBlock`] interface). The remaining bulk operations (i.e. the set
+/// operators, shifts and rotations, compaction, zipping and merging, the `const` constructors,
+/// the `checked_*` accessors, and the lazy or batched insertions) are only available on the
+/// fixed-size variants, so convert into one of those first.
+///
+/// # Example
+///
+/// ```rust
+/// use option_block::Block;
+///
+/// let mut block = Block::<u8, 11>::default();
+/// assert_eq!(Block::<u8, 11>::CAPACITY, 11);
+/// assert!(block.insert(10, 100).is_none());
+/// assert_eq!(block.get(10), Some(&100));
+/// ```
+pub struct Block<T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    data: [MaybeUninit<T>; N],
+    mask: MaskOf<N>,
+}
+
+impl<T, const N: usize> Drop for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn drop(&mut self) {
+        self.clear(); // No memory leaks!
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn clone(&self) -> Self {
+        self.iter_entries().map(|(idx, val)| (idx, val.clone())).collect()
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Block ")?;
+        f.debug_map().entries(self.iter_entries()).finish()
+    }
+}
+
+impl<T, const N: usize> Default for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn default() -> Self {
+        Self { data: [const { MaybeUninit::uninit() }; N], mask: MaskOf::<N>::ZERO }
+    }
+}
+
+/// Create a fully initialized direct-access table.
+impl<T, const N: usize> From<[T; N]> for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn from(vals: [T; N]) -> Self {
+        Self { data: vals.map(MaybeUninit::new), mask: MaskOf::<N>::from_bits(Self::FULL) }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Output = T;
+    fn index(&self, idx: usize) -> &Self::Output {
+        self.get(idx).expect("slot is vacant")
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
+        self.get_mut(idx).expect("slot is vacant")
+    }
+}
+
+impl<T, const N: usize> FromIterator<(usize, T)> for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, T)>,
+    {
+        let mut block = Self::default();
+        for (idx, val) in iter {
+            block.insert(idx, val);
+        }
+        block
+    }
+}
+
+impl<T, const N: usize> IntoIterator for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = T;
+    type IntoIter = iter::BlockIntoIter<T, N>;
+    fn into_iter(self) -> Self::IntoIter {
+        let (data, mask) = self.into_raw_parts();
+        Self::IntoIter { data, mask }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = &'a T;
+    type IntoIter = iter::BlockIter<'a, T, N>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, const N: usize> Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    /// Logical capacity of the fixed-size block, which is exactly `N`.
+    pub const CAPACITY: u32 = N as u32;
+
+    /// Returns the occupancy mask widened into a `u128`.
+    pub(crate) const fn bits(&self) -> u128 {
+        // NOTE: Trait methods cannot be invoked in `const` contexts, so the
+        // mask is widened by reassembling its native-endian bytes instead.
+        let bytes = (&self.mask as *const MaskOf<N>).cast::<u8>();
+        let width = size_of::<MaskOf<N>>();
+        let mut bits = 0;
+        let mut i = 0;
+        while i < width {
+            // SAFETY: The mask is a primitive integer, so all of its bytes are initialized.
+            let byte = unsafe { *bytes.add(i) } as u128;
+            let shift = if cfg!(target_endian = "little") { i } else { width - 1 - i };
+            bits |= byte << (8 * shift);
+            i += 1;
+        }
+        bits
+    }
+
+    /// Overwrites the occupancy mask with the (narrowed) `bits`.
+    fn set_bits(&mut self, bits: u128) {
+        self.mask = MaskOf::<N>::from_bits(bits);
+    }
+
+    /// Disassembles the block into its raw slots and widened occupancy mask without running its
+    /// `Drop` implementation.
+    fn into_raw_parts(self) -> ([MaybeUninit<T>; N], u128) {
+        let block = ManuallyDrop::new(self);
+        // SAFETY: The block is never dropped, so ownership of the slots is simply moved out.
+        let data = unsafe { core::ptr::read(&block.data) };
+        (data, block.bits())
+    }
+
+    /// Moves the value out of the slot at `index` and marks it as vacant.
+    ///
+    /// # Safety
+    /// The slot at `index` **must** be occupied.
+    unsafe fn take_unchecked(&mut self, index: usize) -> T {
+        self.set_bits(self.bits() & !(1 << index));
+        core::mem::replace(&mut self.data[index], MaybeUninit::uninit()).assume_init()
+    }
+
+    /// Mask with exactly the `N` lowest bits set.
+    const FULL: u128 = u128::MAX >> (128 - N);
+
+    /// Checks whether the item at the `index` is vacant (i.e. contains `None`).
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub const fn is_vacant(&self, index: usize) -> bool {
+        check_index!(index < N);
+        self.bits() & (1 << index) == 0
+    }
+
+    /// Returns the number of non-null elements in the block.
+    pub const fn len(&self) -> u32 {
+        self.bits().count_ones()
+    }
+
+    /// Returns `true` if the block contains zero elements.
+    pub const fn is_empty(&self) -> bool {
+        self.bits() == 0
+    }
+
+    /// Returns `true` if all `N` slots of the block are occupied.
+    pub const fn is_full(&self) -> bool {
+        self.bits() == Self::FULL
+    }
+
+    /// Returns the number of vacant slots in the block.
+    pub const fn vacancies(&self) -> u32 {
+        Self::CAPACITY - self.len()
+    }
+
+    /// Returns a copy of the occupancy mask, where bit `i` is set if and only if index `i`
+    /// is occupied. The bits at `N` and above are always cleared.
+    pub const fn occupied_mask(&self) -> MaskOf<N> {
+        self.mask
+    }
+
+    /// Returns a snapshot of the occupancy mask which renders as an `N`-wide bit string
+    /// (index `0` on the left) or as the raw hexadecimal mask. See [`Occupancy`](occupancy::Occupancy).
+    pub const fn occupancy(&self) -> occupancy::Occupancy {
+        occupancy::Occupancy::new(self.bits(), Self::CAPACITY)
+    }
+
+    /// Returns the lowest vacant index (which is always less than `N`), if any.
+    pub const fn lowest_vacant_index(&self) -> Option<usize> {
+        let vacant = !self.bits() & Self::FULL;
+        if vacant == 0 {
+            None
+        } else {
+            Some(vacant.trailing_zeros() as usize)
+        }
+    }
+
+    /// Returns the lowest occupied index, if any.
+    pub const fn lowest_occupied_index(&self) -> Option<usize> {
+        match self.bits() {
+            0 => None,
+            bits => Some(bits.trailing_zeros() as usize),
+        }
+    }
+
+    /// Returns the highest occupied index, if any.
+    pub const fn highest_occupied_index(&self) -> Option<usize> {
+        match self.bits() {
+            0 => None,
+            bits => Some((u128::BITS - 1 - bits.leading_zeros()) as usize),
+        }
+    }
+
+    /// Removes the entry at the lowest occupied index, returning its index and value.
+    /// Repeated calls thus drain the block in ascending index order.
+    pub fn pop_first(&mut self) -> Option<(usize, T)> {
+        let idx = self.lowest_occupied_index()?;
+        // SAFETY: The lowest set bit of the mask is occupied.
+        Some((idx, unsafe { self.take_unchecked(idx) }))
+    }
+
+    /// Removes the entry at the highest occupied index, returning its index and value.
+    /// Repeated calls thus drain the block in descending index order.
+    pub fn pop_last(&mut self) -> Option<(usize, T)> {
+        let idx = self.highest_occupied_index()?;
+        // SAFETY: The highest set bit of the mask is occupied.
+        Some((idx, unsafe { self.take_unchecked(idx) }))
+    }
+
+    /// Inserts the `val` at the [lowest vacant index](Self::lowest_vacant_index), which is
+    /// then returned. If the block is full, the `val` is given back.
+    pub fn push(&mut self, val: T) -> Result<usize, T> {
+        let Some(idx) = self.lowest_vacant_index() else {
+            return Err(val);
+        };
+
+        self.data[idx].write(val);
+        self.set_bits(self.bits() | 1 << idx);
+        Ok(idx)
+    }
+
+    /// Drops every occupied value and empties the block. Only the set bits of the mask are
+    /// visited, so this is cheap for sparse (and especially empty) blocks.
+    pub fn clear(&mut self) {
+        while self.pop_first().is_some() {}
+    }
+
+    /// Drops every occupied value at an index `>= n`, leaving the slots in `0..n` untouched.
+    /// Thus, `truncate(0)` is equivalent to [`clear`](Self::clear) while `n >= N` is a no-op.
+    pub fn truncate(&mut self, n: usize) {
+        while let Some(idx) = self.highest_occupied_index().filter(|&idx| idx >= n) {
+            self.remove(idx);
+        }
+    }
+
+    /// Returns an immutable reference to the value at `index`.
+    /// See the [`get`](Self::get) method for the safe, checked
+    /// version of this method.
+    ///
+    /// # Safety
+    /// The queried value **must** be properly initialized. Otherwise,
+    /// the behavior is undefined.
+    pub const unsafe fn get_unchecked(&self, index: usize) -> &T {
+        self.data[index].assume_init_ref()
+    }
+
+    /// Attempts to retrieve a shared reference to the element at `index`.
+    /// Returns `None` if the slot is vacant (i.e. uninitialized).
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if self.is_vacant(index) {
+            None
+        } else {
+            // SAFETY: We have already verified that the current `index` is not vacant.
+            Some(unsafe { self.get_unchecked(index) })
+        }
+    }
+
+    /// Returns a mutable reference to the value at `index`.
+    /// See the [`get_mut`](Self::get_mut) method for the safe,
+    /// checked version of this method.
+    ///
+    /// # Safety
+    /// The queried value **must** be properly initialized. Otherwise,
+    /// the behavior is undefined.
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        self.data[index].assume_init_mut()
+    }
+
+    /// Attempts to retrieve an exclusive reference to the element at
+    /// `index`. Returns `None` if the slot is vacant (i.e. uninitialized).
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if self.is_vacant(index) {
+            None
+        } else {
+            // SAFETY: We have already verified that the current `index` is not vacant.
+            Some(unsafe { self.get_unchecked_mut(index) })
+        }
+    }
+
+    /// If the slot at the given `index` is already occupied, this method returns a mutable
+    /// reference to the inner data. Otherwise, if the slot is vacant, then this method
+    /// inserts the value constructed by `func`. A mutable reference to the inner data is
+    /// nevertheless returned.
+    pub fn get_or_else(&mut self, index: usize, func: impl FnOnce() -> T) -> &mut T {
+        if self.is_vacant(index) {
+            // SAFETY: Since this slot is initially vacant, then there are no destructors
+            // that need to be run. It should be impossible to leak resources here.
+            self.mask = MaskOf::<N>::from_bits(self.bits() | 1 << index);
+            self.data[index].write(func())
+        } else {
+            // SAFETY: We have already verified that the current `index` is not vacant.
+            unsafe { self.get_unchecked_mut(index) }
+        }
+    }
+
+    /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
+    pub fn get_or(&mut self, index: usize, val: T) -> &mut T {
+        self.get_or_else(index, || val)
+    }
+
+    /// Invokes `func` on the value at `index` only if the slot is occupied. Returns `true`
+    /// if `func` was invoked.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn update(&mut self, index: usize, func: impl FnOnce(&mut T)) -> bool {
+        self.map_occupied(index, func).is_some()
+    }
+
+    /// Invokes `func` on the value at `index` only if the slot is occupied, returning its result.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn map_occupied<R>(&mut self, index: usize, func: impl FnOnce(&mut T) -> R) -> Option<R> {
+        self.get_mut(index).map(func)
+    }
+
+    /// Splits the block into an exclusive reference to the occupied element at `index` and
+    /// an iterator over shared references to every other occupied element (along with their
+    /// indices). Returns `None` if the slot at `index` is vacant.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn split_one_mut(&mut self, index: usize) -> Option<(&mut T, impl Iterator<Item = (usize, &T)>)> {
+        if self.is_vacant(index) {
+            return None;
+        }
+
+        let others = iter::BlockIndices { mask: self.bits() & !(1 << index) };
+        let (head, tail) = self.data.split_at_mut(index);
+        let (target, tail) = tail.split_first_mut()?;
+        let (head, tail): (&[_], &[_]) = (head, tail);
+
+        let others = others.map(move |idx| {
+            let slot = if idx < index { &head[idx] } else { &tail[idx - index - 1] };
+            // SAFETY: The bit at `idx` is set, so the slot has been initialized.
+            (idx, unsafe { slot.assume_init_ref() })
+        });
+
+        // SAFETY: We have already verified that the slot at `index` is not vacant.
+        Some((unsafe { target.assume_init_mut() }, others))
+    }
+
+    /// Inserts the `val` at the `index` only if the slot is vacant, returning a mutable
+    /// reference to the newly inserted value. Otherwise, the occupied slot is left untouched
+    /// and the [error](OccupiedError) gives back the rejected `val`.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn try_insert(&mut self, index: usize, val: T) -> Result<&mut T, OccupiedError<'_, T>> {
+        if self.is_vacant(index) {
+            self.set_bits(self.bits() | 1 << index);
+            Ok(self.data[index].write(val))
+        } else {
+            // SAFETY: We have already verified that the current `index` is not vacant.
+            let existing = unsafe { self.get_unchecked_mut(index) };
+            Err(OccupiedError { index, existing, value: val })
+        }
+    }
+
+    /// Swaps the `val` into the slot at `index` only if it is occupied, returning the old
+    /// value. Otherwise, the block is left untouched and the `val` is given back.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn replace(&mut self, index: usize, val: T) -> Result<T, T> {
+        match self.get_mut(index) {
+            Some(slot) => Ok(core::mem::replace(slot, val)),
+            None => Err(val),
+        }
+    }
+
+    /// Inserts the `val` at the `index`. If a value already exists, it returns `Some`
+    /// containing the old value. Otherwise, it returns `None`.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn insert(&mut self, index: usize, val: T) -> Option<T> {
+        let vacant = self.is_vacant(index);
+        let uninit_val = core::mem::replace(&mut self.data[index], MaybeUninit::new(val));
+        self.mask = MaskOf::<N>::from_bits(self.bits() | 1 << index);
+
+        if vacant {
+            None
+        } else {
+            // SAFETY: The slot was occupied before replacement.
+            // Therefore, it has been initialized properly.
+            Some(unsafe { uninit_val.assume_init() })
+        }
+    }
+
+    /// Removes the value at the `index`. If a value already exists, it returns `Some`
+    /// containing that value. Otherwise, it returns `None`.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if self.is_vacant(index) {
+            return None;
+        }
+
+        let uninit_val = core::mem::replace(&mut self.data[index], MaybeUninit::uninit());
+        self.mask = MaskOf::<N>::from_bits(self.bits() & !(1 << index));
+
+        // SAFETY: We have already verified that the current `index` is not vacant.
+        Some(unsafe { uninit_val.assume_init() })
+    }
+
+    /// Stores `Some` value at the `index` (like [`insert`](Self::insert)) or vacates the slot
+    /// when given `None` (like [`remove`](Self::remove)). In either case, the previous contents
+    /// of the slot are returned.
+    ///
+    /// # Panic
+    /// Panics if `index >= N`, even if the backing mask could hold it.
+    /// With the `unchecked-index` feature, this is only checked in debug builds.
+    pub fn set(&mut self, index: usize, val: Option<T>) -> Option<T> {
+        match val {
+            Some(val) => self.insert(index, val),
+            None => self.remove(index),
+        }
+    }
+
+    /// Replays a stream of [change records](diff::ChangeOwned) onto this block in order.
+    /// On success, returns the number of applied changes. Otherwise, application stops
+    /// at the first change that does not match the current state of the block (e.g.
+    /// removing a vacant slot or an index `>= N`), which is then returned in the
+    /// [error](diff::DiffMismatch). Note that all prior changes remain applied.
+    pub fn apply_diff<I>(&mut self, changes: I) -> Result<usize, diff::DiffMismatch<T>>
+    where
+        I: IntoIterator<Item = diff::ChangeOwned<T>>,
+    {
+        use diff::ChangeOwned;
+        let mut applied = 0;
+        for change in changes {
+            let index = change.index();
+            let is_valid = index < N
+                && match change {
+                    ChangeOwned::Added(..) => self.is_vacant(index),
+                    ChangeOwned::Removed(..) | ChangeOwned::Modified(..) => !self.is_vacant(index),
+                };
+
+            if !is_valid {
+                return Err(diff::DiffMismatch { applied, change });
+            }
+
+            match change {
+                ChangeOwned::Added(idx, val) | ChangeOwned::Modified(idx, val) => drop(self.insert(idx, val)),
+                ChangeOwned::Removed(idx) => drop(self.remove(idx)),
+            }
+
+            applied += 1;
+        }
+
+        Ok(applied)
+    }
+
+    /// Transforms every occupied value (in ascending index order) into a block of another type.
+    /// The occupancy mask is preserved as is.
+    pub fn map<U>(self, mut func: impl FnMut(T) -> U) -> Block<U, N> {
+        self.into_entries().map(|(idx, val)| (idx, func(val))).collect()
+    }
+
+    /// Transforms every occupied entry (in ascending index order) into a block of another type,
+    /// vacating the slots for which `func` returns `None`.
+    pub fn filter_map<U>(self, mut func: impl FnMut(usize, T) -> Option<U>) -> Block<U, N> {
+        self.into_entries().filter_map(|(idx, val)| Some((idx, func(idx, val)?))).collect()
+    }
+
+    /// Converts every occupied value into another type via [`From`], preserving occupancy.
+    pub fn convert<U: From<T>>(self) -> Block<U, N> {
+        self.map(U::from)
+    }
+
+    /// Fallibly converts every occupied value into another type via [`TryFrom`],
+    /// preserving occupancy. Values are converted in ascending index order. The first
+    /// failure aborts the conversion, in which case the failing index and its error are
+    /// returned. All remaining (converted and unconverted) values are dropped.
+    pub fn try_convert<U: TryFrom<T>>(self) -> Result<Block<U, N>, (usize, U::Error)> {
+        self.into_entries().map(|(idx, val)| U::try_from(val).map(|val| (idx, val)).map_err(|err| (idx, err))).collect()
+    }
+
+    /// Create a by-reference iterator for this block.
+    pub fn iter(&self) -> iter::BlockIter<'_, T, N> {
+        iter::BlockIter { data: &self.data, mask: self.bits() }
+    }
+
+    /// Create a by-reference iterator over the occupied entries along with their (actual)
+    /// slot indices.
+    pub fn iter_entries(&self) -> iter::BlockEntries<'_, T, N> {
+        iter::BlockEntries { data: &self.data, mask: self.bits() }
+    }
+
+    /// Create a mutable by-reference iterator over the occupied entries along with their
+    /// (actual) slot indices.
+    pub fn iter_entries_mut(&mut self) -> iter::BlockEntriesMut<'_, T, N> {
+        let mask = self.bits();
+        iter::BlockEntriesMut { slots: self.data.iter_mut().enumerate(), mask }
+    }
+
+    /// Consume the block into a by-value iterator over the occupied entries along with their
+    /// (actual) slot indices. Collecting this iterator moves the entries losslessly.
+    pub fn into_entries(self) -> iter::BlockIntoEntries<T, N> {
+        iter::BlockIntoEntries { inner: self.into_iter() }
+    }
+
+    /// Create an iterator over the occupied indices in ascending order. The values themselves
+    /// are never touched, which makes this handy for index-only traversals.
+    pub const fn occupied_indices(&self) -> iter::BlockIndices {
+        iter::BlockIndices { mask: self.bits() }
+    }
+
+    /// Create a draining iterator which removes every entry (along with its index) from
+    /// the block. The block is empty once the iterator is dropped, even if it was not
+    /// fully consumed.
+    pub fn drain(&mut self) -> iter::BlockDrain<'_, T, N> {
+        iter::BlockDrain { block: self }
+    }
+
+    /// Create an iterator which removes and yields (along with their indices) only the
+    /// entries for which `pred` returns `true`. Non-matching entries are left untouched.
+    /// If the iterator is dropped early, the unvisited entries remain in the block.
+    pub fn extract_if<F>(&mut self, pred: F) -> iter::BlockExtractIf<'_, T, F, N>
+    where
+        F: FnMut(usize, &mut T) -> bool,
+    {
+        let pending = self.bits();
+        iter::BlockExtractIf { block: self, pending, pred }
+    }
+
+    /// Create an iterator over every slot of this block (including vacant ones). Each slot
+    /// is yielded as a proxy that may inspect, modify, take, or insert its value.
+    pub fn slots_mut(&mut self) -> iter::BlockSlotsMut<'_, T, N> {
+        iter::BlockSlotsMut {
+            slots: self.data.iter_mut().enumerate(),
+            mask: core::cell::Cell::from_mut(&mut self.mask),
+        }
+    }
+
+    /// Moves every entry into a block of another type, provided that every occupied index
+    /// fits within the target `capacity`. Otherwise, the original block is returned.
+    fn try_move_into<B: Default>(mut self, capacity: u32, mut insert: impl FnMut(&mut B, usize, T)) -> Result<B, Self> {
+        if capacity < 128 && self.bits() >> capacity != 0 {
+            return Err(self);
+        }
+
+        let mut block = B::default();
+        while let Some((idx, val)) = self.pop_first() {
+            insert(&mut block, idx, val);
+        }
+
+        Ok(block)
+    }
+}
+
+impl<T: PartialEq, const N: usize> Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    /// Lazily computes the [changes](diff::Change) that transform `self` into `other`,
+    /// in ascending index order. Unchanged slots are skipped.
+    pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = diff::Change<'a, T>> + 'a {
+        use diff::Change;
+        let candidates = iter::BlockIndices { mask: self.bits() | other.bits() };
+        candidates.filter_map(move |idx| match (self.get(idx), other.get(idx)) {
+            (None, Some(new)) => Some(Change::Added(idx, new)),
+            (Some(_), None) => Some(Change::Removed(idx)),
+            (Some(old), Some(new)) if old != new => Some(Change::Modified(idx, new)),
+            _ => None,
+        })
+    }
+}
+
+impl<T: Default, const N: usize> Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
+    pub fn get_or_default(&mut self, index: usize) -> &mut T {
+        self.get_or_else(index, Default::default)
+    }
+}
+
+impl<T, const N: usize> FixedBlock for Block<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = T;
+    type Rebind<U> = Block<U, N>;
+    const CAPACITY: u32 = N as u32;
+
+    fn is_vacant(&self, index: usize) -> bool {
+        self.is_vacant(index)
+    }
+
+    fn len(&self) -> u32 {
+        self.len()
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        self.get(index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(index)
+    }
+
+    fn insert(&mut self, index: usize, val: T) -> Option<T> {
+        self.insert(index, val)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        self.remove(index)
+    }
+}
+
+macro_rules! impl_fixed_conversions {
+    ($($name:ident)*) => {$(
+        /// Moves every entry into the fixed-size variant. Fails (returning the original block)
+        /// if any occupied index does not fit in the target.
+        impl<T, const N: usize> TryFrom<Block<T, N>> for $name<T>
+        where
+            Capacity<N>: SupportedCapacity,
+        {
+            type Error = Block<T, N>;
+            fn try_from(block: Block<T, N>) -> Result<Self, Self::Error> {
+                block.try_move_into($name::<T>::CAPACITY, |dst: &mut Self, idx, val| {
+                    dst.insert(idx, val);
+                })
+            }
+        }
+
+        /// Moves every entry out of the fixed-size variant. Fails (returning the original block)
+        /// if any occupied index is not less than `N`.
+        impl<T, const N: usize> TryFrom<$name<T>> for Block<T, N>
+        where
+            Capacity<N>: SupportedCapacity,
+        {
+            type Error = $name<T>;
+            fn try_from(mut block: $name<T>) -> Result<Self, Self::Error> {
+                if (N..$name::<T>::CAPACITY as usize).any(|idx| !block.is_vacant(idx)) {
+                    return Err(block);
+                }
+
+                let mut exact = Self::default();
+                for idx in 0..N.min($name::<T>::CAPACITY as usize) {
+                    if let Some(val) = block.remove(idx) {
+                        exact.insert(idx, val);
+                    }
+                }
+
+                Ok(exact)
+            }
+        }
+    )*};
+}
+
+impl_fixed_conversions!(Block8 Block16 Block32 Block64 Block128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_tests() {
+        use core::mem::size_of;
+        assert_eq!(size_of::<Block<u8, 1>>(), 1 + 1);
+        assert_eq!(size_of::<Block<u8, 11>>(), 11 + 2 + 1); // Padding for `u16` alignment
+        assert!(size_of::<Block<u8, 11>>() < size_of::<Block16<u8>>());
+        assert_eq!(size_of::<Block<u8, 64>>(), 64 + 8);
+        assert_eq!(size_of::<Block<u8, 128>>(), 128 + 16);
+    }
+
+    #[test]
+    fn logical_capacity() {
+        fn check<const N: usize>()
+        where
+            Capacity<N>: SupportedCapacity,
+        {
+            assert_eq!(Block::<usize, N>::CAPACITY as usize, N);
+            let mut block = Block::<usize, N>::default();
+            assert_eq!(block.lowest_vacant_index(), Some(0));
+
+            while let Some(idx) = block.lowest_vacant_index() {
+                assert!(idx < N);
+                assert!(block.insert(idx, idx).is_none());
+            }
+
+            assert!(block.is_full());
+            assert_eq!(block.len() as usize, N);
+            assert!(block.iter().copied().eq(0..N));
+        }
+
+        check::<1>();
+        check::<11>();
+        check::<64>();
+        check::<128>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn boundary_index_rejection() {
+        let mut block = Block::<u8, 11>::default();
+        block.insert(11, 0);
+    }
+
+    #[test]
+    fn fixed_conversions() {
+        let block: Block<u8, 11> = [(0, 0), (10, 10)].into_iter().collect();
+        let fixed = Block16::try_from(block).unwrap();
+        assert_eq!(fixed.get(10), Some(&10));
+
+        let fixed = Block8::try_from(Block::<u8, 11>::try_from(fixed).unwrap()).unwrap_err();
+        assert_eq!(fixed.len(), 2);
+
+        let mut fixed = Block16::try_from(fixed).unwrap();
+        assert!(fixed.insert(11, 11).is_none());
+        let fixed = Block::<u8, 11>::try_from(fixed).unwrap_err();
+        assert_eq!(fixed.get(11), Some(&11));
+
+        let block = Block::<u8, 128>::try_from(fixed).unwrap();
+        assert_eq!(block.iter().len(), 3);
+        assert!(block.into_iter().eq([0, 10, 11]));
+    }
+
+    #[test]
+    fn entry_iterators() {
+        let mut block: Block<u8, 11> = [(1, 1), (4, 4), (10, 10)].into_iter().collect();
+        assert!(block.iter_entries().eq([(1, &1), (4, &4), (10, &10)]));
+        assert!(block.occupied_indices().rev().eq([10, 4, 1]));
+        assert_eq!(block.occupied_mask(), 0b100_0001_0010);
+        assert_eq!(block.iter().last(), Some(&10));
+        assert_eq!(block.vacancies(), 8);
+
+        for (idx, val) in block.iter_entries_mut() {
+            *val += idx as u8;
+        }
+        assert!(block.iter().copied().eq([2, 8, 20]));
+
+        let mut iter = block.iter();
+        iter.next();
+        assert!(iter.clone().eq([&8, &20]));
+
+        assert!(block.extract_if(|_, val| *val == 8).eq([(4, 8)]));
+        assert_eq!(block.pop_last(), Some((10, 20)));
+        assert_eq!(block.push(5), Ok(0));
+        assert!(block.drain().eq([(0, 5), (1, 2)]));
+        assert!(block.is_empty());
+    }
+
+    #[test]
+    fn fixed_block_interop() {
+        use crate::keyed::KeyedBlock;
+
+        let mut map = KeyedBlock::<u16, char, Block<u16, 3>>::default();
+        assert_eq!(map.insert(300, 'c'), Ok(None));
+        assert_eq!(map.insert(10, 'a'), Ok(None));
+        assert_eq!(map.insert(20, 'b'), Ok(None));
+        assert_eq!(map.insert(5, 'z'), Err((5, 'z')));
+        assert!(map.iter().eq([(&10, &'a'), (&20, &'b'), (&300, &'c')]));
+
+        let mut exact = Block::<u8, 11>::default();
+        let fixed: Block8<u8> = [(0, 0), (7, 7)].into_iter().collect();
+        fixed.move_into(&mut exact).unwrap();
+        assert!(exact.into_entries().eq([(0, 0), (7, 7)]));
+    }
+
+    #[test]
+    fn full_array_conversions() {
+        fn check<const N: usize>()
+        where
+            Capacity<N>: SupportedCapacity,
+        {
+            let block = Block::<usize, N>::from(core::array::from_fn(|idx| idx));
+            assert_eq!(block.len() as usize, N);
+            assert!(block.is_full());
+            assert_eq!(block.vacancies(), 0);
+            assert_eq!(block.lowest_vacant_index(), None);
+            assert_eq!(block.iter().count(), N);
+            assert!(block.iter().copied().eq(0..N));
+            drop(block);
+        }
+
+        check::<1>();
+        check::<11>();
+        check::<64>();
+        check::<128>();
+    }
+
+    #[test]
+    fn const_accessors() {
+        const fn summary(block: &Block<u8, 11>) -> (u32, bool, Option<usize>, Option<usize>) {
+            let last = block.highest_occupied_index();
+            (block.len(), block.is_vacant(0), block.lowest_vacant_index(), last)
+        }
+
+        let block: Block<u8, 11> = [(0, 0), (1, 1), (7, 7)].into_iter().collect();
+        assert_eq!(summary(&block), (3, false, Some(2), Some(7)));
+        assert_eq!(block.occupied_mask(), 0b1000_0011);
+    }
+
+    #[test]
+    fn fixed_parity_methods() {
+        use crate::diff::Change;
+
+        let old: Block<u16, 11> = [(0, 1), (3, 300), (10, 10)].into_iter().collect();
+        let new: Block<u16, 11> = [(3, 3), (5, 5), (10, 10)].into_iter().collect();
+        let mut copy = old.clone();
+        assert_eq!(copy.apply_diff(old.diff(&new).map(Change::to_owned)), Ok(3));
+        assert!(copy.iter_entries().eq(new.iter_entries()));
+
+        let err = copy.apply_diff([crate::diff::ChangeOwned::Removed(11)]).unwrap_err();
+        assert_eq!(err.applied, 0);
+
+        let converted: Block<u32, 11> = copy.clone().convert();
+        assert!(converted.into_entries().eq([(3, 3), (5, 5), (10, 10)]));
+        assert_eq!(copy.clone().try_convert::<u8>().map(|block| block.len()), Ok(3));
+        assert_eq!(old.try_convert::<u8>().unwrap_err().0, 3);
+
+        let (target, others) = copy.split_one_mut(5).unwrap();
+        assert!(others.eq([(3, &3), (10, &10)]));
+        *target = 50;
+        assert!(copy.split_one_mut(4).is_none());
+
+        for mut slot in copy.slots_mut() {
+            match slot.index() % 2 {
+                0 => drop(slot.insert(slot.index() as u16)),
+                _ => drop(slot.take()),
+            }
+        }
+        assert!(copy.iter_entries().eq([(0, &0), (2, &2), (4, &4), (6, &6), (8, &8), (10, &10)]));
+        assert_eq!(copy.slots_mut().len(), 11);
+    }
+}
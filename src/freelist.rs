@@ -0,0 +1,187 @@
+//! A block which reuses freed slots in LIFO order. See [`FreelistBlock`](FreelistBlock).
+
+use crate::FixedBlock;
+
+/// A slot allocator over a block `B` which hands out the most recently freed slot first.
+/// When the free list is empty, never-used slots are handed out in ascending order.
+///
+/// The free list is intrusive: for every freed slot, a parallel block of `u8` links
+/// stores the index of the next freed slot (if any). Hence, the occupied slots of the
+/// link block are exactly the members of the free list.
+///
+/// # Example
+///
+/// ```rust
+/// use option_block::{freelist::FreelistBlock, Block8};
+///
+/// let mut block = FreelistBlock::<_, Block8<_>>::default();
+/// assert_eq!(block.allocate('a'), Ok(0));
+/// assert_eq!(block.allocate('b'), Ok(1));
+/// assert_eq!(block.allocate('c'), Ok(2));
+///
+/// assert_eq!(block.free(0), Some('a'));
+/// assert_eq!(block.free(2), Some('c'));
+/// assert_eq!(block.allocate('d'), Ok(2));
+/// assert_eq!(block.allocate('e'), Ok(0));
+/// assert_eq!(block.allocate('f'), Ok(3));
+/// ```
+pub struct FreelistBlock<T, B: FixedBlock<Item = T>> {
+    block: B,
+    links: B::Rebind<u8>,
+    head: Option<u8>,
+    watermark: u8,
+}
+
+impl<T, B: FixedBlock<Item = T>> Default for FreelistBlock<T, B> {
+    fn default() -> Self {
+        Self { block: B::default(), links: Default::default(), head: None, watermark: 0 }
+    }
+}
+
+impl<T, B: FixedBlock<Item = T>> FreelistBlock<T, B> {
+    /// Returns a shared reference to the underlying block, which may be used for
+    /// occupancy queries and iteration.
+    pub const fn as_block(&self) -> &B {
+        &self.block
+    }
+
+    /// Consumes the allocator and returns the underlying block.
+    pub fn into_block(self) -> B {
+        self.block
+    }
+
+    /// Returns the number of allocated slots.
+    pub fn len(&self) -> u32 {
+        self.block.len()
+    }
+
+    /// Returns `true` if no slots are allocated.
+    pub fn is_empty(&self) -> bool {
+        self.block.is_empty()
+    }
+
+    /// Checks whether the slot at the `index` is vacant (i.e. not allocated).
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`.
+    pub fn is_vacant(&self, index: usize) -> bool {
+        self.block.is_vacant(index)
+    }
+
+    /// Attempts to retrieve a shared reference to the element at `index`.
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.block.get(index)
+    }
+
+    /// Attempts to retrieve an exclusive reference to the element at `index`.
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.block.get_mut(index)
+    }
+
+    /// Stores the `val` in the most recently freed slot. If there are none, the lowest
+    /// never-used slot is chosen instead. Returns the chosen index, or gives the `val`
+    /// back if the block is full.
+    pub fn allocate(&mut self, val: T) -> Result<usize, T> {
+        let index = if let Some(head) = self.head {
+            let index = usize::from(head);
+            self.head = self.links.remove(index);
+            index
+        } else if u32::from(self.watermark) < B::CAPACITY {
+            let index = usize::from(self.watermark);
+            self.watermark += 1;
+            index
+        } else {
+            return Err(val);
+        };
+
+        let prev = self.block.insert(index, val);
+        debug_assert!(prev.is_none());
+        Ok(index)
+    }
+
+    /// Removes the value at the `index` and pushes the slot onto the free list.
+    /// Returns `None` (without modifying the free list) if the slot is already vacant.
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`.
+    pub fn free(&mut self, index: usize) -> Option<T> {
+        let val = self.block.remove(index)?;
+        if let Some(head) = self.head {
+            self.links.insert(index, head);
+        }
+        // NOTE: All indices fit in a `u8` since the capacity is at most 128.
+        self.head = Some(index as u8);
+        Some(val)
+    }
+
+    /// Iterates over the free list, from the most recently freed slot.
+    pub fn free_list(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut head = self.head;
+        core::iter::from_fn(move || {
+            let index = usize::from(head?);
+            head = self.links.get(index).copied();
+            Some(index)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Block16;
+
+    /// Every vacant slot below the watermark must be on the free list exactly once.
+    fn assert_consistent<T, B: FixedBlock<Item = T>>(block: &FreelistBlock<T, B>) {
+        let mut listed = 0u128;
+        for index in block.free_list() {
+            assert!(block.is_vacant(index));
+            assert_eq!(listed & (1 << index), 0);
+            listed |= 1 << index;
+        }
+
+        for index in 0..usize::from(block.watermark) {
+            assert_eq!(block.is_vacant(index), listed & (1 << index) != 0);
+        }
+    }
+
+    #[test]
+    fn lifo_reuse_order() {
+        let mut block = FreelistBlock::<u32, Block16<u32>>::default();
+        for expected in 0..6 {
+            assert_eq!(block.allocate(expected as u32), Ok(expected));
+        }
+
+        for index in [1, 4, 2] {
+            assert_eq!(block.free(index), Some(index as u32));
+            assert_consistent(&block);
+        }
+
+        assert!(block.free(4).is_none());
+        assert!(block.free_list().eq([2, 4, 1]));
+
+        assert_eq!(block.allocate(20), Ok(2));
+        assert_eq!(block.free(0), Some(0));
+        assert_eq!(block.allocate(0), Ok(0));
+        assert_eq!(block.allocate(40), Ok(4));
+        assert_eq!(block.allocate(10), Ok(1));
+        assert_eq!(block.allocate(6), Ok(6));
+        assert_consistent(&block);
+
+        for expected in 7..16 {
+            assert_eq!(block.allocate(0), Ok(expected));
+        }
+        assert_eq!(block.allocate(99), Err(99));
+        assert_eq!(block.len(), 16);
+
+        assert_eq!(block.free(15), Some(0));
+        assert_eq!(block.allocate(15), Ok(15));
+        assert_consistent(&block);
+        assert!(block.as_block().iter().take(7).eq(&[0, 10, 20, 3, 40, 5, 6]));
+    }
+}
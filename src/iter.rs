@@ -1,62 +1,1474 @@
-//! By-value and by-reference iterator objects for the various block variants.
-//! Note that these types cannot be used directly. They are simply part of the
-//! public interface just in case one needs to explicitly "name" the iterator
-//! object in their code.
-//!
-//! # Example
-//!
-//! ```rust
-//! let block: option_block::Block8<_> = [10, 8, 1].into_iter().enumerate().collect();
-//! assert_eq!(block.get(0), Some(&10));
-//! assert_eq!(block.get(1), Some(&8));
-//! assert_eq!(block.get(2), Some(&1));
-//! assert!(block.get(3).is_none());
-//! ```
-
-use core::ops::Range;
-
-macro_rules! impl_iterator_outer {
-    ($name:ident $into_iter:ident $iter:ident) => {
-        /// By-value iterator that consumes the block allocation.
-        pub struct $into_iter<T> {
-            pub(crate) block: $crate::$name<T>,
-            pub(crate) index: Range<usize>,
-        }
-
-        impl<T> Iterator for $into_iter<T> {
-            type Item = T;
-            fn next(&mut self) -> Option<Self::Item> {
-                Some(loop {
-                    let idx = self.index.next()?;
-                    if let Some(val) = self.block.remove(idx) {
-                        break val;
-                    }
-                })
-            }
-        }
-
-        /// By-reference iterator that borrows from the block allocation.
-        pub struct $iter<'a, T> {
-            pub(crate) block: &'a $crate::$name<T>,
-            pub(crate) index: Range<usize>,
-        }
-
-        impl<'a, T> Iterator for $iter<'a, T> {
-            type Item = &'a T;
-            fn next(&mut self) -> Option<Self::Item> {
-                Some(loop {
-                    let idx = self.index.next()?;
-                    if let Some(val) = self.block.get(idx) {
-                        break val;
-                    }
-                })
-            }
-        }
-    };
-}
-
-impl_iterator_outer!(Block8 Block8IntoIter Block8Iter);
-impl_iterator_outer!(Block16 Block16IntoIter Block16Iter);
-impl_iterator_outer!(Block32 Block32IntoIter Block32Iter);
-impl_iterator_outer!(Block64 Block64IntoIter Block64Iter);
-impl_iterator_outer!(Block128 Block128IntoIter Block128Iter);
+//! By-value and by-reference iterator objects for the various block variants.
+//! Note that these types cannot be used directly. They are simply part of the
+//! public interface just in case one needs to explicitly "name" the iterator
+//! object in their code.
+//!
+//! # Example
+//!
+//! ```rust
+//! let block: option_block::Block8<_> = [10, 8, 1].into_iter().enumerate().collect();
+//! assert_eq!(block.get(0), Some(&10));
+//! assert_eq!(block.get(1), Some(&8));
+//! assert_eq!(block.get(2), Some(&1));
+//! assert!(block.get(3).is_none());
+//! ```
+
+use crate::exact::{sealed::Mask, Capacity, SupportedCapacity};
+use core::{
+    cell::Cell,
+    fmt,
+    iter::{Enumerate, FusedIterator},
+    mem::MaybeUninit,
+    ops::Range,
+    slice,
+};
+
+/// Keeps only the bits of a (widened) `mask` that still lie within the `index` window.
+fn window_mask(mask: u128, index: &Range<usize>) -> u128 {
+    if index.is_empty() {
+        return 0;
+    }
+    let window = mask >> index.start;
+    let window = match index.len() {
+        len @ 0..128 => window & ((1 << len) - 1),
+        _ => window,
+    };
+    window << index.start
+}
+
+/// Counts the occupied slots of a (widened) `mask` that still lie within the `index` window.
+fn count_occupied(mask: u128, index: &Range<usize>) -> usize {
+    window_mask(mask, index).count_ones() as usize
+}
+
+macro_rules! impl_iterator_outer {
+    ($name:ident $into_iter:ident $iter:ident $drain:ident $extract_if:ident $entries:ident $entries_mut:ident $into_entries:ident $int:ty) => {
+        /// By-value iterator that consumes the block allocation. It takes ownership of the raw
+        /// slots along with the live mask, so only the occupied slots are ever visited.
+        pub struct $into_iter<T> {
+            pub(crate) data: [MaybeUninit<T>; <$int>::BITS as usize],
+            pub(crate) mask: $int,
+        }
+
+        impl<T> $into_iter<T> {
+            /// Moves the value at `idx` out of the iterator and clears its bit.
+            ///
+            /// # Safety
+            /// The bit at `idx` must be set in the live mask.
+            unsafe fn take(&mut self, idx: usize) -> (usize, T) {
+                self.mask &= !(1 << idx);
+                // SAFETY: The bit was set, so the slot has been initialized. Since the bit is now
+                // cleared, the value will never be read (or dropped) again.
+                (idx, unsafe { self.data.get_unchecked(idx).assume_init_read() })
+            }
+
+            fn pop_front(&mut self) -> Option<(usize, T)> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = self.mask.trailing_zeros() as usize;
+                // SAFETY: The lowest set bit is at `idx`.
+                Some(unsafe { self.take(idx) })
+            }
+
+            fn pop_back(&mut self) -> Option<(usize, T)> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = (<$int>::BITS - 1 - self.mask.leading_zeros()) as usize;
+                // SAFETY: The highest set bit is at `idx`.
+                Some(unsafe { self.take(idx) })
+            }
+        }
+
+        impl<T> Drop for $into_iter<T> {
+            fn drop(&mut self) {
+                while self.mask != 0 {
+                    let idx = self.mask.trailing_zeros() as usize;
+                    self.mask &= self.mask - 1;
+                    // SAFETY: The bit was set, so the slot has been initialized and not yet moved.
+                    unsafe { self.data.get_unchecked_mut(idx).assume_init_drop() };
+                }
+            }
+        }
+
+        impl<T> Iterator for $into_iter<T> {
+            type Item = T;
+            fn next(&mut self) -> Option<Self::Item> {
+                self.pop_front().map(|(_, val)| val)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.mask.count_ones() as usize;
+                (len, Some(len))
+            }
+
+            fn count(self) -> usize {
+                // The remaining elements are dropped along with `self`.
+                self.mask.count_ones() as usize
+            }
+
+            fn last(mut self) -> Option<Self::Item> {
+                self.next_back()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                for _ in 0..n {
+                    // Skipped elements are dropped right away.
+                    self.pop_front()?;
+                }
+                self.next()
+            }
+        }
+
+        impl<T> ExactSizeIterator for $into_iter<T> {}
+        impl<T> FusedIterator for $into_iter<T> {}
+
+        impl<T> DoubleEndedIterator for $into_iter<T> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.pop_back().map(|(_, val)| val)
+            }
+        }
+
+        impl<T: Clone> Clone for $into_iter<T> {
+            fn clone(&self) -> Self {
+                // Only the slots that are still owned by the iterator are deep-cloned.
+                let mut block = $crate::$name::default();
+                let mut mask = self.mask;
+                while mask != 0 {
+                    let idx = mask.trailing_zeros() as usize;
+                    mask &= mask - 1;
+                    // SAFETY: The bit was set, so the slot has been initialized.
+                    let val = unsafe { self.data.get_unchecked(idx).assume_init_ref() };
+                    block.insert(idx, val.clone());
+                }
+                block.into_iter()
+            }
+        }
+
+        impl<T> fmt::Debug for $into_iter<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($into_iter))
+                    .field("mask", &format_args!("{:#b}", self.mask))
+                    .field("len", &self.len())
+                    .finish()
+            }
+        }
+
+        /// By-value iterator over the occupied entries (along with their indices). Entries
+        /// not yet yielded are dropped together with the iterator.
+        pub struct $into_entries<T> {
+            pub(crate) inner: $into_iter<T>,
+        }
+
+        impl<T> Iterator for $into_entries<T> {
+            type Item = (usize, T);
+            fn next(&mut self) -> Option<Self::Item> {
+                self.inner.pop_front()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.inner.size_hint()
+            }
+
+            fn count(self) -> usize {
+                self.inner.count()
+            }
+
+            fn last(mut self) -> Option<Self::Item> {
+                self.inner.pop_back()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                for _ in 0..n {
+                    self.inner.pop_front()?;
+                }
+                self.next()
+            }
+        }
+
+        impl<T> ExactSizeIterator for $into_entries<T> {}
+        impl<T> FusedIterator for $into_entries<T> {}
+
+        impl<T: Clone> Clone for $into_entries<T> {
+            fn clone(&self) -> Self {
+                Self { inner: self.inner.clone() }
+            }
+        }
+
+        impl<T> fmt::Debug for $into_entries<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($into_entries))
+                    .field("mask", &format_args!("{:#b}", self.inner.mask))
+                    .field("len", &self.len())
+                    .finish()
+            }
+        }
+
+        /// By-reference iterator that borrows from the block allocation. Only the set bits of
+        /// a shrinking copy of the mask are visited, so vacant slots are skipped in constant time.
+        pub struct $iter<'a, T> {
+            pub(crate) data: &'a [MaybeUninit<T>],
+            pub(crate) mask: $int,
+        }
+
+        impl<'a, T> Iterator for $iter<'a, T> {
+            type Item = &'a T;
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = self.mask.trailing_zeros() as usize;
+                self.mask &= self.mask - 1;
+                // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+                Some(unsafe { self.data.get_unchecked(idx).assume_init_ref() })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.mask.count_ones() as usize;
+                (len, Some(len))
+            }
+
+            fn count(self) -> usize {
+                self.mask.count_ones() as usize
+            }
+
+            fn last(self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = (<$int>::BITS - 1 - self.mask.leading_zeros()) as usize;
+                // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+                Some(unsafe { self.data.get_unchecked(idx).assume_init_ref() })
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                for _ in 0..n {
+                    if self.mask == 0 {
+                        return None;
+                    }
+                    self.mask &= self.mask - 1;
+                }
+                self.next()
+            }
+        }
+
+        impl<T> ExactSizeIterator for $iter<'_, T> {}
+        impl<T> FusedIterator for $iter<'_, T> {}
+
+        impl<T> Clone for $iter<'_, T> {
+            fn clone(&self) -> Self {
+                Self { data: self.data, mask: self.mask }
+            }
+        }
+
+        impl<T> fmt::Debug for $iter<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($iter))
+                    .field("mask", &format_args!("{:#b}", self.mask))
+                    .field("len", &self.len())
+                    .finish()
+            }
+        }
+
+        /// By-reference iterator over the occupied entries (along with their indices).
+        pub struct $entries<'a, T> {
+            pub(crate) data: &'a [MaybeUninit<T>],
+            pub(crate) mask: $int,
+        }
+
+        impl<'a, T> Iterator for $entries<'a, T> {
+            type Item = (usize, &'a T);
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = self.mask.trailing_zeros() as usize;
+                self.mask &= self.mask - 1;
+                // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+                Some((idx, unsafe { self.data.get_unchecked(idx).assume_init_ref() }))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.mask.count_ones() as usize;
+                (len, Some(len))
+            }
+
+            fn count(self) -> usize {
+                self.mask.count_ones() as usize
+            }
+
+            fn last(self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = (<$int>::BITS - 1 - self.mask.leading_zeros()) as usize;
+                // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+                Some((idx, unsafe { self.data.get_unchecked(idx).assume_init_ref() }))
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                for _ in 0..n {
+                    if self.mask == 0 {
+                        return None;
+                    }
+                    self.mask &= self.mask - 1;
+                }
+                self.next()
+            }
+        }
+
+        impl<T> ExactSizeIterator for $entries<'_, T> {}
+        impl<T> FusedIterator for $entries<'_, T> {}
+
+        impl<T> Clone for $entries<'_, T> {
+            fn clone(&self) -> Self {
+                Self { data: self.data, mask: self.mask }
+            }
+        }
+
+        impl<T> fmt::Debug for $entries<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($entries))
+                    .field("mask", &format_args!("{:#b}", self.mask))
+                    .field("len", &self.len())
+                    .finish()
+            }
+        }
+
+        /// Mutable by-reference iterator over the occupied entries (along with their indices).
+        pub struct $entries_mut<'a, T> {
+            pub(crate) slots: Enumerate<slice::IterMut<'a, MaybeUninit<T>>>,
+            pub(crate) mask: $int,
+        }
+
+        impl<'a, T> Iterator for $entries_mut<'a, T> {
+            type Item = (usize, &'a mut T);
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let target = self.mask.trailing_zeros() as usize;
+                self.mask &= self.mask - 1;
+
+                // Jump straight over the vacant slots in between.
+                let cursor = <$int>::BITS as usize - self.slots.len();
+                let (idx, slot) = self.slots.nth(target - cursor)?;
+                debug_assert_eq!(idx, target);
+
+                // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+                Some((idx, unsafe { slot.assume_init_mut() }))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.mask.count_ones() as usize;
+                (len, Some(len))
+            }
+
+            fn count(self) -> usize {
+                self.mask.count_ones() as usize
+            }
+
+            fn last(mut self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                // Strip every bit except the highest one.
+                self.mask = 1 << (<$int>::BITS - 1 - self.mask.leading_zeros());
+                self.next()
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                for _ in 0..n {
+                    if self.mask == 0 {
+                        return None;
+                    }
+                    self.mask &= self.mask - 1;
+                }
+                self.next()
+            }
+        }
+
+        impl<T> ExactSizeIterator for $entries_mut<'_, T> {}
+        impl<T> FusedIterator for $entries_mut<'_, T> {}
+
+        impl<T> fmt::Debug for $entries_mut<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($entries_mut))
+                    .field("mask", &format_args!("{:#b}", self.mask))
+                    .field("len", &self.len())
+                    .finish()
+            }
+        }
+
+        /// Draining iterator that removes every entry (along with its index) from the block.
+        /// Dropping the iterator drops the remaining entries, leaving the block empty. If the
+        /// iterator is leaked instead, the remaining entries simply stay in the block.
+        pub struct $drain<'a, T> {
+            pub(crate) block: &'a mut $crate::$name<T>,
+        }
+
+        impl<T> Iterator for $drain<'_, T> {
+            type Item = (usize, T);
+            fn next(&mut self) -> Option<Self::Item> {
+                let idx = self.block.lowest_occupied_index()?;
+                let val = self.block.remove(idx)?;
+                Some((idx, val))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.block.len() as usize;
+                (len, Some(len))
+            }
+        }
+
+        impl<T> ExactSizeIterator for $drain<'_, T> {}
+        impl<T> FusedIterator for $drain<'_, T> {}
+
+        impl<T> Drop for $drain<'_, T> {
+            fn drop(&mut self) {
+                self.block.clear();
+            }
+        }
+
+        impl<T> fmt::Debug for $drain<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($drain))
+                    .field("mask", &format_args!("{:#b}", self.block.mask))
+                    .field("len", &self.len())
+                    .finish()
+            }
+        }
+
+        /// Iterator which removes (and yields) only the entries that match a predicate.
+        /// Dropping the iterator early leaves the unvisited entries in the block.
+        pub struct $extract_if<'a, T, F> {
+            pub(crate) block: &'a mut $crate::$name<T>,
+            pub(crate) index: Range<usize>,
+            pub(crate) pred: F,
+        }
+
+        impl<T, F> Iterator for $extract_if<'_, T, F>
+        where
+            F: FnMut(usize, &mut T) -> bool,
+        {
+            type Item = (usize, T);
+            fn next(&mut self) -> Option<Self::Item> {
+                Some(loop {
+                    let idx = self.index.next()?;
+                    let Some(val) = self.block.get_mut(idx) else {
+                        continue;
+                    };
+                    if (self.pred)(idx, val) {
+                        break (idx, self.block.remove(idx)?);
+                    }
+                })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (0, Some(count_occupied(self.block.mask.into(), &self.index)))
+            }
+        }
+
+        impl<T, F> FusedIterator for $extract_if<'_, T, F> where F: FnMut(usize, &mut T) -> bool {}
+
+        impl<T, F> fmt::Debug for $extract_if<'_, T, F> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                // Only the occupied slots that have yet to be visited are shown.
+                let mask = window_mask(self.block.mask.into(), &self.index);
+                f.debug_struct(stringify!($extract_if)).field("mask", &format_args!("{mask:#b}")).finish()
+            }
+        }
+    };
+}
+
+macro_rules! impl_slots_outer {
+    ($slots_mut:ident $slot_mut:ident $int:ty) => {
+        /// Iterator over every slot of a block (vacant or not) in ascending index order.
+        /// Each slot is yielded as a proxy which grants full control over its occupancy.
+        pub struct $slots_mut<'a, T> {
+            pub(crate) slots: Enumerate<slice::IterMut<'a, MaybeUninit<T>>>,
+            pub(crate) mask: &'a Cell<$int>,
+        }
+
+        impl<'a, T> Iterator for $slots_mut<'a, T> {
+            type Item = $slot_mut<'a, T>;
+            fn next(&mut self) -> Option<Self::Item> {
+                let (index, slot) = self.slots.next()?;
+                Some($slot_mut { index, slot, mask: self.mask })
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.slots.size_hint()
+            }
+        }
+
+        impl<T> ExactSizeIterator for $slots_mut<'_, T> {}
+        impl<T> FusedIterator for $slots_mut<'_, T> {}
+
+        impl<T> fmt::Debug for $slots_mut<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($slots_mut))
+                    .field("mask", &format_args!("{:#b}", self.mask.get()))
+                    .field("len", &self.len())
+                    .finish()
+            }
+        }
+
+        /// Exclusive proxy to a single slot of a block. The parent block's mask is kept
+        /// consistent after every operation.
+        pub struct $slot_mut<'a, T> {
+            index: usize,
+            slot: &'a mut MaybeUninit<T>,
+            mask: &'a Cell<$int>,
+        }
+
+        impl<T> $slot_mut<'_, T> {
+            /// Returns the index of this slot within the parent block.
+            pub const fn index(&self) -> usize {
+                self.index
+            }
+
+            /// Checks whether this slot is vacant (i.e. contains `None`).
+            pub fn is_vacant(&self) -> bool {
+                self.mask.get() & (1 << self.index) == 0
+            }
+
+            /// Returns a shared reference to the value, if any.
+            pub fn get(&self) -> Option<&T> {
+                if self.is_vacant() {
+                    None
+                } else {
+                    // SAFETY: We have already verified that this slot is not vacant.
+                    Some(unsafe { self.slot.assume_init_ref() })
+                }
+            }
+
+            /// Returns an exclusive reference to the value, if any.
+            pub fn get_mut(&mut self) -> Option<&mut T> {
+                if self.is_vacant() {
+                    None
+                } else {
+                    // SAFETY: We have already verified that this slot is not vacant.
+                    Some(unsafe { self.slot.assume_init_mut() })
+                }
+            }
+
+            /// Removes the value from this slot, leaving it vacant.
+            pub fn take(&mut self) -> Option<T> {
+                if self.is_vacant() {
+                    return None;
+                }
+
+                self.mask.set(self.mask.get() & !(1 << self.index));
+                let uninit_val = core::mem::replace(self.slot, MaybeUninit::uninit());
+
+                // SAFETY: We have already verified that this slot was not vacant.
+                Some(unsafe { uninit_val.assume_init() })
+            }
+
+            /// Inserts the `val` into this slot. If a value already exists, it returns
+            /// `Some` containing the old value. Otherwise, it returns `None`.
+            pub fn insert(&mut self, val: T) -> Option<T> {
+                let vacant = self.is_vacant();
+                let uninit_val = core::mem::replace(self.slot, MaybeUninit::new(val));
+                self.mask.set(self.mask.get() | 1 << self.index);
+
+                if vacant {
+                    None
+                } else {
+                    // SAFETY: The slot was occupied before replacement.
+                    // Therefore, it has been initialized properly.
+                    Some(unsafe { uninit_val.assume_init() })
+                }
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $slot_mut<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($slot_mut)).field("index", &self.index).field("value", &self.get()).finish()
+            }
+        }
+    };
+}
+
+macro_rules! impl_indices_outer {
+    ($indices:ident $int:ty) => {
+        /// Iterator over the occupied indices of a block in ascending order. Only the set bits
+        /// of a copy of the mask are visited, so the block's data is never touched.
+        #[derive(Debug, Clone)]
+        pub struct $indices {
+            pub(crate) mask: $int,
+        }
+
+        impl Iterator for $indices {
+            type Item = usize;
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = self.mask.trailing_zeros();
+                self.mask &= self.mask - 1;
+                Some(idx as usize)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.mask.count_ones() as usize;
+                (len, Some(len))
+            }
+        }
+
+        impl DoubleEndedIterator for $indices {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.mask == 0 {
+                    return None;
+                }
+                let idx = <$int>::BITS - 1 - self.mask.leading_zeros();
+                self.mask &= !(1 << idx);
+                Some(idx as usize)
+            }
+        }
+
+        impl ExactSizeIterator for $indices {}
+        impl FusedIterator for $indices {}
+    };
+}
+impl_iterator_outer! {
+    Block8 Block8IntoIter Block8Iter Block8Drain Block8ExtractIf
+    Block8Entries Block8EntriesMut Block8IntoEntries u8
+}
+impl_iterator_outer! {
+    Block16 Block16IntoIter Block16Iter Block16Drain Block16ExtractIf
+    Block16Entries Block16EntriesMut Block16IntoEntries u16
+}
+impl_iterator_outer! {
+    Block32 Block32IntoIter Block32Iter Block32Drain Block32ExtractIf
+    Block32Entries Block32EntriesMut Block32IntoEntries u32
+}
+impl_iterator_outer! {
+    Block64 Block64IntoIter Block64Iter Block64Drain Block64ExtractIf
+    Block64Entries Block64EntriesMut Block64IntoEntries u64
+}
+impl_iterator_outer! {
+    Block128 Block128IntoIter Block128Iter Block128Drain Block128ExtractIf
+    Block128Entries Block128EntriesMut Block128IntoEntries u128
+}
+
+impl_indices_outer!(Block8Indices u8);
+impl_indices_outer!(Block16Indices u16);
+impl_indices_outer!(Block32Indices u32);
+impl_indices_outer!(Block64Indices u64);
+impl_indices_outer!(Block128Indices u128);
+
+impl_slots_outer!(Block8SlotsMut Block8SlotMut u8);
+impl_slots_outer!(Block16SlotsMut Block16SlotMut u16);
+impl_slots_outer!(Block32SlotsMut Block32SlotMut u32);
+impl_slots_outer!(Block64SlotsMut Block64SlotMut u64);
+impl_slots_outer!(Block128SlotsMut Block128SlotMut u128);
+
+/// Forwards an iterator method to whichever variant is currently wrapped.
+macro_rules! dispatch_next {
+    ($iter:expr) => {
+        dispatch_next!($iter, next)
+    };
+    ($iter:expr, $method:ident) => {
+        match $iter {
+            Self::Block8(iter) => iter.$method(),
+            Self::Block16(iter) => iter.$method(),
+            Self::Block32(iter) => iter.$method(),
+            Self::Block64(iter) => iter.$method(),
+            Self::Block128(iter) => iter.$method(),
+        }
+    };
+}
+
+/// By-value iterator that consumes an [`AnyBlock`](crate::any::AnyBlock).
+pub enum AnyBlockIntoIter<T> {
+    Block8(Block8IntoIter<T>),
+    Block16(Block16IntoIter<T>),
+    Block32(Block32IntoIter<T>),
+    Block64(Block64IntoIter<T>),
+    Block128(Block128IntoIter<T>),
+}
+
+impl<T> Iterator for AnyBlockIntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        dispatch_next!(self)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        dispatch_next!(self, size_hint)
+    }
+}
+
+impl<T> ExactSizeIterator for AnyBlockIntoIter<T> {}
+impl<T> FusedIterator for AnyBlockIntoIter<T> {}
+
+impl<T: Clone> Clone for AnyBlockIntoIter<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Block8(iter) => Self::Block8(iter.clone()),
+            Self::Block16(iter) => Self::Block16(iter.clone()),
+            Self::Block32(iter) => Self::Block32(iter.clone()),
+            Self::Block64(iter) => Self::Block64(iter.clone()),
+            Self::Block128(iter) => Self::Block128(iter.clone()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for AnyBlockIntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Block8(iter) => f.debug_tuple("Block8").field(iter).finish(),
+            Self::Block16(iter) => f.debug_tuple("Block16").field(iter).finish(),
+            Self::Block32(iter) => f.debug_tuple("Block32").field(iter).finish(),
+            Self::Block64(iter) => f.debug_tuple("Block64").field(iter).finish(),
+            Self::Block128(iter) => f.debug_tuple("Block128").field(iter).finish(),
+        }
+    }
+}
+
+/// By-reference iterator that borrows from an [`AnyBlock`](crate::any::AnyBlock).
+pub enum AnyBlockIter<'a, T> {
+    Block8(Block8Iter<'a, T>),
+    Block16(Block16Iter<'a, T>),
+    Block32(Block32Iter<'a, T>),
+    Block64(Block64Iter<'a, T>),
+    Block128(Block128Iter<'a, T>),
+}
+
+impl<'a, T> Iterator for AnyBlockIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        dispatch_next!(self)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        dispatch_next!(self, size_hint)
+    }
+}
+
+impl<T> ExactSizeIterator for AnyBlockIter<'_, T> {}
+impl<T> FusedIterator for AnyBlockIter<'_, T> {}
+
+impl<T> Clone for AnyBlockIter<'_, T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Block8(iter) => Self::Block8(iter.clone()),
+            Self::Block16(iter) => Self::Block16(iter.clone()),
+            Self::Block32(iter) => Self::Block32(iter.clone()),
+            Self::Block64(iter) => Self::Block64(iter.clone()),
+            Self::Block128(iter) => Self::Block128(iter.clone()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for AnyBlockIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Block8(iter) => f.debug_tuple("Block8").field(iter).finish(),
+            Self::Block16(iter) => f.debug_tuple("Block16").field(iter).finish(),
+            Self::Block32(iter) => f.debug_tuple("Block32").field(iter).finish(),
+            Self::Block64(iter) => f.debug_tuple("Block64").field(iter).finish(),
+            Self::Block128(iter) => f.debug_tuple("Block128").field(iter).finish(),
+        }
+    }
+}
+
+/// Index of the highest set bit of a nonzero (widened) `mask`.
+fn highest_bit(mask: u128) -> usize {
+    (u128::BITS - 1 - mask.leading_zeros()) as usize
+}
+
+/// By-value iterator that consumes a [`Block`](crate::Block) allocation. Like the fixed-width
+/// iterators, it takes ownership of the raw slots along with the live (widened) mask.
+pub struct BlockIntoIter<T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) data: [MaybeUninit<T>; N],
+    pub(crate) mask: u128,
+}
+
+impl<T, const N: usize> BlockIntoIter<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    /// Moves the value at `idx` out of the iterator and clears its bit.
+    ///
+    /// # Safety
+    /// The bit at `idx` must be set in the live mask.
+    unsafe fn take(&mut self, idx: usize) -> (usize, T) {
+        self.mask &= !(1 << idx);
+        // SAFETY: The bit was set, so the slot has been initialized. Since the bit is now
+        // cleared, the value will never be read (or dropped) again.
+        (idx, unsafe { self.data.get_unchecked(idx).assume_init_read() })
+    }
+
+    fn pop_front(&mut self) -> Option<(usize, T)> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = self.mask.trailing_zeros() as usize;
+        // SAFETY: The lowest set bit is at `idx`.
+        Some(unsafe { self.take(idx) })
+    }
+
+    fn pop_back(&mut self) -> Option<(usize, T)> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = highest_bit(self.mask);
+        // SAFETY: The highest set bit is at `idx`.
+        Some(unsafe { self.take(idx) })
+    }
+}
+
+impl<T, const N: usize> Drop for BlockIntoIter<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn drop(&mut self) {
+        while self.mask != 0 {
+            let idx = self.mask.trailing_zeros() as usize;
+            self.mask &= self.mask - 1;
+            // SAFETY: The bit was set, so the slot has been initialized and not yet moved.
+            unsafe { self.data.get_unchecked_mut(idx).assume_init_drop() };
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for BlockIntoIter<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pop_front().map(|(_, val)| val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        // The remaining elements are dropped along with `self`.
+        self.mask.count_ones() as usize
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            // Skipped elements are dropped right away.
+            self.pop_front()?;
+        }
+        self.next()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for BlockIntoIter<T, N> where Capacity<N>: SupportedCapacity {}
+impl<T, const N: usize> FusedIterator for BlockIntoIter<T, N> where Capacity<N>: SupportedCapacity {}
+
+impl<T, const N: usize> DoubleEndedIterator for BlockIntoIter<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.pop_back().map(|(_, val)| val)
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for BlockIntoIter<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn clone(&self) -> Self {
+        // Only the slots that are still owned by the iterator are deep-cloned.
+        let mut block = crate::Block::<T, N>::default();
+        let mut mask = self.mask;
+        while mask != 0 {
+            let idx = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+            // SAFETY: The bit was set, so the slot has been initialized.
+            let val = unsafe { self.data.get_unchecked(idx).assume_init_ref() };
+            block.insert(idx, val.clone());
+        }
+        block.into_iter()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BlockIntoIter<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockIntoIter")
+            .field("mask", &format_args!("{:#b}", self.mask))
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// By-value iterator over the occupied entries (along with their indices) of a
+/// [`Block`](crate::Block). Entries not yet yielded are dropped together with the iterator.
+pub struct BlockIntoEntries<T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) inner: BlockIntoIter<T, N>,
+}
+
+impl<T, const N: usize> Iterator for BlockIntoEntries<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = (usize, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.inner.count()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.inner.pop_back()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            self.inner.pop_front()?;
+        }
+        self.next()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for BlockIntoEntries<T, N> where Capacity<N>: SupportedCapacity {}
+impl<T, const N: usize> FusedIterator for BlockIntoEntries<T, N> where Capacity<N>: SupportedCapacity {}
+
+impl<T: Clone, const N: usize> Clone for BlockIntoEntries<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone() }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BlockIntoEntries<T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockIntoEntries")
+            .field("mask", &format_args!("{:#b}", self.inner.mask))
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// By-reference iterator that borrows from a [`Block`](crate::Block) allocation. Only the set
+/// bits of a shrinking copy of the mask are visited, so vacant slots are skipped in constant time.
+pub struct BlockIter<'a, T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) data: &'a [MaybeUninit<T>; N],
+    pub(crate) mask: u128,
+}
+
+impl<'a, T, const N: usize> Iterator for BlockIter<'a, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = self.mask.trailing_zeros() as usize;
+        self.mask &= self.mask - 1;
+        // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+        Some(unsafe { self.data.get_unchecked(idx).assume_init_ref() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        // SAFETY: The highest set bit marks an initialized slot.
+        Some(unsafe { self.data.get_unchecked(highest_bit(self.mask)).assume_init_ref() })
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            if self.mask == 0 {
+                return None;
+            }
+            self.mask &= self.mask - 1;
+        }
+        self.next()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for BlockIter<'_, T, N> where Capacity<N>: SupportedCapacity {}
+impl<T, const N: usize> FusedIterator for BlockIter<'_, T, N> where Capacity<N>: SupportedCapacity {}
+
+impl<T, const N: usize> Clone for BlockIter<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn clone(&self) -> Self {
+        Self { data: self.data, mask: self.mask }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BlockIter<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockIter").field("mask", &format_args!("{:#b}", self.mask)).field("len", &self.len()).finish()
+    }
+}
+
+/// By-reference iterator over the occupied entries (along with their indices) of a
+/// [`Block`](crate::Block).
+pub struct BlockEntries<'a, T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) data: &'a [MaybeUninit<T>; N],
+    pub(crate) mask: u128,
+}
+
+impl<'a, T, const N: usize> Iterator for BlockEntries<'a, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = (usize, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = self.mask.trailing_zeros() as usize;
+        self.mask &= self.mask - 1;
+        // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+        Some((idx, unsafe { self.data.get_unchecked(idx).assume_init_ref() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = highest_bit(self.mask);
+        // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+        Some((idx, unsafe { self.data.get_unchecked(idx).assume_init_ref() }))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            if self.mask == 0 {
+                return None;
+            }
+            self.mask &= self.mask - 1;
+        }
+        self.next()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for BlockEntries<'_, T, N> where Capacity<N>: SupportedCapacity {}
+impl<T, const N: usize> FusedIterator for BlockEntries<'_, T, N> where Capacity<N>: SupportedCapacity {}
+
+impl<T, const N: usize> Clone for BlockEntries<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn clone(&self) -> Self {
+        Self { data: self.data, mask: self.mask }
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BlockEntries<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockEntries")
+            .field("mask", &format_args!("{:#b}", self.mask))
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Mutable by-reference iterator over the occupied entries (along with their indices) of a
+/// [`Block`](crate::Block).
+pub struct BlockEntriesMut<'a, T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) slots: Enumerate<slice::IterMut<'a, MaybeUninit<T>>>,
+    pub(crate) mask: u128,
+}
+
+impl<'a, T, const N: usize> Iterator for BlockEntriesMut<'a, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = (usize, &'a mut T);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let target = self.mask.trailing_zeros() as usize;
+        self.mask &= self.mask - 1;
+
+        // Jump straight over the vacant slots in between.
+        let cursor = N - self.slots.len();
+        let (idx, slot) = self.slots.nth(target - cursor)?;
+        debug_assert_eq!(idx, target);
+
+        // SAFETY: The bit at `idx` was set, so the slot has been initialized.
+        Some((idx, unsafe { slot.assume_init_mut() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        // Strip every bit except the highest one.
+        self.mask = 1 << highest_bit(self.mask);
+        self.next()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            if self.mask == 0 {
+                return None;
+            }
+            self.mask &= self.mask - 1;
+        }
+        self.next()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for BlockEntriesMut<'_, T, N> where Capacity<N>: SupportedCapacity {}
+impl<T, const N: usize> FusedIterator for BlockEntriesMut<'_, T, N> where Capacity<N>: SupportedCapacity {}
+
+impl<T, const N: usize> fmt::Debug for BlockEntriesMut<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockEntriesMut")
+            .field("mask", &format_args!("{:#b}", self.mask))
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Iterator over the occupied indices of a [`Block`](crate::Block) in ascending order. Only the
+/// set bits of a copy of the (widened) mask are visited, so the block's data is never touched.
+#[derive(Debug, Clone)]
+pub struct BlockIndices {
+    pub(crate) mask: u128,
+}
+
+impl Iterator for BlockIndices {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = self.mask.trailing_zeros();
+        self.mask &= self.mask - 1;
+        Some(idx as usize)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for BlockIndices {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+        let idx = highest_bit(self.mask);
+        self.mask &= !(1 << idx);
+        Some(idx)
+    }
+}
+
+impl ExactSizeIterator for BlockIndices {}
+impl FusedIterator for BlockIndices {}
+
+/// Draining iterator that removes every entry (along with its index) from a
+/// [`Block`](crate::Block). Dropping the iterator drops the remaining entries, leaving the
+/// block empty. If the iterator is leaked instead, the remaining entries simply stay in the block.
+pub struct BlockDrain<'a, T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) block: &'a mut crate::Block<T, N>,
+}
+
+impl<T, const N: usize> Iterator for BlockDrain<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = (usize, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.block.pop_first()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.block.len() as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for BlockDrain<'_, T, N> where Capacity<N>: SupportedCapacity {}
+impl<T, const N: usize> FusedIterator for BlockDrain<'_, T, N> where Capacity<N>: SupportedCapacity {}
+
+impl<T, const N: usize> Drop for BlockDrain<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn drop(&mut self) {
+        self.block.clear();
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BlockDrain<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockDrain")
+            .field("mask", &format_args!("{:#b}", self.block.bits()))
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Iterator which removes (and yields) only the entries of a [`Block`](crate::Block) that
+/// match a predicate. Dropping the iterator early leaves the unvisited entries in the block.
+pub struct BlockExtractIf<'a, T, F, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) block: &'a mut crate::Block<T, N>,
+    pub(crate) pending: u128,
+    pub(crate) pred: F,
+}
+
+impl<T, F, const N: usize> Iterator for BlockExtractIf<'_, T, F, N>
+where
+    Capacity<N>: SupportedCapacity,
+    F: FnMut(usize, &mut T) -> bool,
+{
+    type Item = (usize, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending != 0 {
+            let idx = self.pending.trailing_zeros() as usize;
+            self.pending &= self.pending - 1;
+            let Some(val) = self.block.get_mut(idx) else {
+                continue;
+            };
+            if (self.pred)(idx, val) {
+                return Some((idx, self.block.remove(idx)?));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.pending.count_ones() as usize))
+    }
+}
+
+impl<T, F, const N: usize> FusedIterator for BlockExtractIf<'_, T, F, N>
+where
+    Capacity<N>: SupportedCapacity,
+    F: FnMut(usize, &mut T) -> bool,
+{
+}
+
+impl<T, F, const N: usize> fmt::Debug for BlockExtractIf<'_, T, F, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockExtractIf").field("mask", &format_args!("{:#b}", self.pending)).finish()
+    }
+}
+
+/// Iterator over every slot of a [`Block`](crate::Block) (vacant or not) in ascending index
+/// order. Each slot is yielded as a proxy which grants full control over its occupancy.
+pub struct BlockSlotsMut<'a, T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    pub(crate) slots: Enumerate<slice::IterMut<'a, MaybeUninit<T>>>,
+    pub(crate) mask: &'a Cell<<Capacity<N> as SupportedCapacity>::Mask>,
+}
+
+impl<'a, T, const N: usize> Iterator for BlockSlotsMut<'a, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    type Item = BlockSlotMut<'a, T, N>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, slot) = self.slots.next()?;
+        Some(BlockSlotMut { index, slot, mask: self.mask })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.slots.size_hint()
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for BlockSlotsMut<'_, T, N> where Capacity<N>: SupportedCapacity {}
+impl<T, const N: usize> FusedIterator for BlockSlotsMut<'_, T, N> where Capacity<N>: SupportedCapacity {}
+
+impl<T, const N: usize> fmt::Debug for BlockSlotsMut<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockSlotsMut")
+            .field("mask", &format_args!("{:#b}", self.mask.get().to_bits()))
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Exclusive proxy to a single slot of a [`Block`](crate::Block). The parent block's mask is
+/// kept consistent after every operation.
+pub struct BlockSlotMut<'a, T, const N: usize>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    index: usize,
+    slot: &'a mut MaybeUninit<T>,
+    mask: &'a Cell<<Capacity<N> as SupportedCapacity>::Mask>,
+}
+
+impl<T, const N: usize> BlockSlotMut<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    /// Returns the index of this slot within the parent block.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Overwrites the bit of this slot in the parent block's mask.
+    fn set_occupied(&self, occupied: bool) {
+        let bits = self.mask.get().to_bits();
+        let bits = if occupied { bits | 1 << self.index } else { bits & !(1 << self.index) };
+        self.mask.set(Mask::from_bits(bits));
+    }
+
+    /// Checks whether this slot is vacant (i.e. contains `None`).
+    pub fn is_vacant(&self) -> bool {
+        self.mask.get().to_bits() & (1 << self.index) == 0
+    }
+
+    /// Returns a shared reference to the value, if any.
+    pub fn get(&self) -> Option<&T> {
+        if self.is_vacant() {
+            None
+        } else {
+            // SAFETY: We have already verified that this slot is not vacant.
+            Some(unsafe { self.slot.assume_init_ref() })
+        }
+    }
+
+    /// Returns an exclusive reference to the value, if any.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if self.is_vacant() {
+            None
+        } else {
+            // SAFETY: We have already verified that this slot is not vacant.
+            Some(unsafe { self.slot.assume_init_mut() })
+        }
+    }
+
+    /// Removes the value from this slot, leaving it vacant.
+    pub fn take(&mut self) -> Option<T> {
+        if self.is_vacant() {
+            return None;
+        }
+
+        self.set_occupied(false);
+        let uninit_val = core::mem::replace(self.slot, MaybeUninit::uninit());
+
+        // SAFETY: We have already verified that this slot was not vacant.
+        Some(unsafe { uninit_val.assume_init() })
+    }
+
+    /// Inserts the `val` into this slot. If a value already exists, it returns
+    /// `Some` containing the old value. Otherwise, it returns `None`.
+    pub fn insert(&mut self, val: T) -> Option<T> {
+        let vacant = self.is_vacant();
+        let uninit_val = core::mem::replace(self.slot, MaybeUninit::new(val));
+        self.set_occupied(true);
+
+        if vacant {
+            None
+        } else {
+            // SAFETY: The slot was occupied before replacement.
+            // Therefore, it has been initialized properly.
+            Some(unsafe { uninit_val.assume_init() })
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for BlockSlotMut<'_, T, N>
+where
+    Capacity<N>: SupportedCapacity,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockSlotMut").field("index", &self.index).field("value", &self.get()).finish()
+    }
+}
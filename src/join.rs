@@ -0,0 +1,161 @@
+//! Joins over any number of blocks sharing the same index space. See [`join_blocks!`](crate::join_blocks)
+//! for more details.
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use core::{
+    mem::MaybeUninit,
+    slice::{Iter, IterMut},
+};
+
+/// Conversion of a (shared or exclusive) block reference into one of the operands of
+/// [`join_blocks!`](crate::join_blocks). This is an implementation detail of the macro.
+pub trait IntoJoinPart {
+    /// The operand type, which yields the references to the joined values.
+    type Part: JoinPart;
+
+    /// Converts the block reference into a join operand.
+    fn into_part(self) -> Self::Part;
+}
+
+/// One of the operands of [`join_blocks!`](crate::join_blocks). This is an implementation
+/// detail of the macro.
+pub trait JoinPart {
+    /// Reference to a joined value.
+    type Item;
+
+    /// Returns the occupancy mask of the underlying block (widened to a `u128`).
+    fn mask(&self) -> u128;
+
+    /// Fetches the reference to the value at `index`.
+    ///
+    /// # Safety
+    /// The slot at `index` **must** be occupied. Moreover, the `index` **must** be strictly
+    /// greater than that of the previous call (if any).
+    unsafe fn fetch(&mut self, index: usize) -> Self::Item;
+}
+
+/// Join operand for a shared block reference.
+pub struct SharedPart<'a, T> {
+    slots: Iter<'a, MaybeUninit<T>>,
+    cursor: usize,
+    mask: u128,
+}
+
+impl<'a, T> JoinPart for SharedPart<'a, T> {
+    type Item = &'a T;
+
+    fn mask(&self) -> u128 {
+        self.mask
+    }
+
+    unsafe fn fetch(&mut self, index: usize) -> Self::Item {
+        // Jump straight over the slots in between.
+        let slot = self.slots.nth(index - self.cursor).unwrap_unchecked();
+        self.cursor = index + 1;
+        slot.assume_init_ref()
+    }
+}
+
+/// Join operand for an exclusive block reference.
+pub struct ExclusivePart<'a, T> {
+    slots: IterMut<'a, MaybeUninit<T>>,
+    cursor: usize,
+    mask: u128,
+}
+
+impl<'a, T> JoinPart for ExclusivePart<'a, T> {
+    type Item = &'a mut T;
+
+    fn mask(&self) -> u128 {
+        self.mask
+    }
+
+    unsafe fn fetch(&mut self, index: usize) -> Self::Item {
+        // Jump straight over the slots in between.
+        let slot = self.slots.nth(index - self.cursor).unwrap_unchecked();
+        self.cursor = index + 1;
+        slot.assume_init_mut()
+    }
+}
+
+macro_rules! impl_into_join_part {
+    ($($name:ident)*) => {$(
+        impl<'a, T> IntoJoinPart for &'a $name<T> {
+            type Part = SharedPart<'a, T>;
+            fn into_part(self) -> Self::Part {
+                SharedPart { slots: self.data.iter(), cursor: 0, mask: self.mask as u128 }
+            }
+        }
+
+        impl<'a, T> IntoJoinPart for &'a mut $name<T> {
+            type Part = ExclusivePart<'a, T>;
+            fn into_part(self) -> Self::Part {
+                let mask = self.mask as u128;
+                ExclusivePart { slots: self.data.iter_mut(), cursor: 0, mask }
+            }
+        }
+    )*};
+}
+
+impl_into_join_part!(Block8 Block16 Block32 Block64 Block128);
+
+/// Joins any number (up to eight) of shared (`&block`) and exclusive (`&mut block`) block
+/// references over the indices occupied in **all** of them. The resulting iterator yields a
+/// tuple of the index followed by one reference per block (in ascending index order). Only
+/// the set bits of the joint mask are visited. Blocks of different widths may be joined, in
+/// which case only the indices of the narrowest block are considered.
+///
+/// # Example
+///
+/// ```rust
+/// use option_block::{join_blocks, Block64};
+///
+/// let mut positions: Block64<i32> = [(0, 0), (1, 10), (5, 50)].into_iter().collect();
+/// let velocities: Block64<i32> = [(1, 1), (5, 5), (9, 9)].into_iter().collect();
+/// let mut ticks: Block64<u8> = [(0, 0), (5, 0), (9, 0)].into_iter().collect();
+///
+/// for (idx, pos, vel, tick) in join_blocks!(&mut positions, &velocities, &mut ticks) {
+///     assert_eq!(idx, 5);
+///     *pos += vel;
+///     *tick += 1;
+/// }
+///
+/// assert_eq!(positions.get(5), Some(&55));
+/// assert_eq!(ticks.get(5), Some(&1));
+/// ```
+///
+/// The usual borrowing rules apply to the operands, so the same block cannot be joined both
+/// mutably and immutably (or mutably twice):
+///
+/// ```rust,compile_fail
+/// use option_block::{join_blocks, Block8};
+///
+/// let mut block = Block8::<i32>::default();
+/// for (_, a, b) in join_blocks!(&mut block, &block) {
+///     *a += b;
+/// }
+/// ```
+#[macro_export]
+macro_rules! join_blocks {
+    ($($block:expr),+ $(,)?) => {
+        $crate::join_blocks!(@bind [] [__part0 __part1 __part2 __part3 __part4 __part5 __part6 __part7] $($block),+)
+    };
+    (@bind [$($bound:tt)*] [$name:ident $($names:ident)*] $block:expr $(, $rest:expr)*) => {
+        $crate::join_blocks!(@bind [$($bound)* ($name $block)] [$($names)*] $($rest),*)
+    };
+    (@bind [$(($name:ident $block:expr))+] [$($names:ident)*]) => {{
+        $(let mut $name = $crate::join::IntoJoinPart::into_part($block);)+
+        let mut mask = u128::MAX $(& $crate::join::JoinPart::mask(&$name))+;
+        ::core::iter::from_fn(move || {
+            if mask == 0 {
+                return None;
+            }
+            let idx = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+
+            // SAFETY: The slot at `idx` is occupied in every block, and the indices are
+            // visited in strictly ascending order.
+            Some((idx, $(unsafe { $crate::join::JoinPart::fetch(&mut $name, idx) }),+))
+        })
+    }};
+}
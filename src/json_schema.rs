@@ -0,0 +1,33 @@
+//! [`schemars::JsonSchema`] implementations for the block variants. The schema describes the
+//! dense `serde` representation: a fixed-length array of exactly `CAPACITY` nullable entries.
+
+extern crate alloc;
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use alloc::{borrow::Cow, format};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+macro_rules! impl_json_schema {
+    ($($name:ident)*) => {$(
+        impl<T: JsonSchema> JsonSchema for $name<T> {
+            fn schema_name() -> Cow<'static, str> {
+                format!(concat!(stringify!($name), "_of_{}"), T::schema_name()).into()
+            }
+
+            fn schema_id() -> Cow<'static, str> {
+                format!(concat!("option_block::", stringify!($name), "<{}>"), T::schema_id()).into()
+            }
+
+            fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+                json_schema!({
+                    "type": "array",
+                    "items": generator.subschema_for::<Option<T>>(),
+                    "minItems": Self::CAPACITY,
+                    "maxItems": Self::CAPACITY,
+                })
+            }
+        }
+    )*};
+}
+
+impl_json_schema!(Block8 Block16 Block32 Block64 Block128);
@@ -0,0 +1,141 @@
+//! A tiny fixed-capacity sorted map. See [`KeyedBlock`](KeyedBlock) for more details.
+
+use crate::FixedBlock;
+
+/// A fixed-capacity map which stores its keys and values in two parallel blocks of the same
+/// width as `B`. The entries are kept densely packed at the lowest indices and sorted by key,
+/// so lookups are a binary search over the occupied prefix. Insertions and removals shift the
+/// entries above the affected index, much like a sorted `Vec`.
+///
+/// # Example
+///
+/// ```rust
+/// use option_block::{keyed::KeyedBlock, Block8};
+///
+/// let mut map = KeyedBlock::<u16, char, Block8<u16>>::default();
+/// assert_eq!(map.insert(300, 'c'), Ok(None));
+/// assert_eq!(map.insert(10, 'a'), Ok(None));
+/// assert_eq!(map.insert(300, 'z'), Ok(Some('c')));
+///
+/// assert_eq!(map.get(&10), Some(&'a'));
+/// assert!(map.iter().eq([(&10, &'a'), (&300, &'z')]));
+/// ```
+pub struct KeyedBlock<K, V, B: FixedBlock<Item = K>> {
+    keys: B,
+    values: B::Rebind<V>,
+}
+
+impl<K, V, B: FixedBlock<Item = K>> Default for KeyedBlock<K, V, B> {
+    fn default() -> Self {
+        Self { keys: B::default(), values: Default::default() }
+    }
+}
+
+impl<K, V, B: FixedBlock<Item = K>> KeyedBlock<K, V, B> {
+    /// Maximum number of entries in the map.
+    pub const CAPACITY: u32 = B::CAPACITY;
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> u32 {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the map contains zero entries.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the key at the `index` of the dense prefix.
+    fn key_at(&self, index: usize) -> &K {
+        self.keys.get(index).expect("keys must be densely packed")
+    }
+
+    /// Moves the entry at `from` into the (vacant) slot at `to`.
+    fn move_entry(&mut self, from: usize, to: usize) {
+        if let Some(key) = self.keys.remove(from) {
+            self.keys.insert(to, key);
+        }
+        if let Some(val) = self.values.remove(from) {
+            self.values.insert(to, val);
+        }
+    }
+
+    /// Create an iterator over the entries of the map in ascending key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        (0..self.len() as usize).map(|idx| {
+            let key = self.key_at(idx);
+            let val = self.values.get(idx).expect("values must be densely packed");
+            (key, val)
+        })
+    }
+}
+
+impl<K: Ord, V, B: FixedBlock<Item = K>> KeyedBlock<K, V, B> {
+    /// Binary searches the dense prefix for the `key`. See [`slice::binary_search`].
+    fn search(&self, key: &K) -> Result<usize, usize> {
+        let (mut lo, mut hi) = (0, self.len() as usize);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match self.key_at(mid).cmp(key) {
+                core::cmp::Ordering::Less => lo = mid + 1,
+                core::cmp::Ordering::Greater => hi = mid,
+                core::cmp::Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(lo)
+    }
+
+    /// Returns a shared reference to the value associated with the `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let idx = self.search(key).ok()?;
+        self.values.get(idx)
+    }
+
+    /// Returns an exclusive reference to the value associated with the `key`.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let idx = self.search(key).ok()?;
+        self.values.get_mut(idx)
+    }
+
+    /// Returns `true` if the map contains an entry for the `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.search(key).is_ok()
+    }
+
+    /// Inserts the `val` for the `key`. If an entry already exists, its old value is returned in
+    /// `Ok(Some(_))`. If the map is full and the `key` is new, the pair is handed back via `Err`.
+    pub fn insert(&mut self, key: K, val: V) -> Result<Option<V>, (K, V)> {
+        let idx = match self.search(&key) {
+            Ok(idx) => return Ok(self.values.insert(idx, val)),
+            Err(idx) => idx,
+        };
+
+        let len = self.len() as usize;
+        if len >= Self::CAPACITY as usize {
+            return Err((key, val));
+        }
+
+        for src in (idx..len).rev() {
+            self.move_entry(src, src + 1);
+        }
+
+        self.keys.insert(idx, key);
+        self.values.insert(idx, val);
+        Ok(None)
+    }
+
+    /// Removes the entry for the `key`, returning its value (if any).
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let idx = self.search(key).ok()?;
+        let len = self.len() as usize;
+
+        self.keys.remove(idx);
+        let val = self.values.remove(idx);
+
+        for src in idx + 1..len {
+            self.move_entry(src, src - 1);
+        }
+
+        val
+    }
+}
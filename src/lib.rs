@@ -1,17 +1,140 @@
 #![no_std]
 #![doc = include_str!("../README.md")]
 
+/// Validates an index against the capacity of a block. By default, this is a full `assert!`.
+/// With the `unchecked-index` feature, this degrades into a `debug_assert!` so that release
+/// builds may skip the check entirely.
+#[cfg(not(feature = "unchecked-index"))]
+macro_rules! check_index {
+    ($cond:expr) => {
+        assert!($cond)
+    };
+}
+
+#[cfg(feature = "unchecked-index")]
+macro_rules! check_index {
+    ($cond:expr) => {
+        debug_assert!($cond)
+    };
+}
+
+pub mod any;
+#[cfg(feature = "quickcheck")]
+mod arbitrary;
+#[cfg(feature = "bincode")]
+mod bincode_codec;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "minicbor")]
+mod cbor;
+pub mod diff;
+pub mod exact;
+pub mod freelist;
 pub mod iter;
+pub mod join;
+#[cfg(feature = "schemars")]
+mod json_schema;
+pub mod keyed;
+pub mod niche;
+pub mod occupancy;
+#[cfg(feature = "bytemuck")]
+pub mod pod;
+#[cfg(feature = "serde")]
+mod serde_dense;
+#[cfg(feature = "serde")]
+pub mod serde_sparse;
+#[cfg(feature = "ufmt")]
+mod udebug;
+#[cfg(feature = "zerocopy")]
+pub mod zerocopy_bytes;
 
 use core::{
-    mem::MaybeUninit,
-    ops::{Index, IndexMut},
+    mem::{ManuallyDrop, MaybeUninit},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Index, IndexMut, Sub, SubAssign},
 };
 
+/// Common interface over the fixed-size block variants. This is mainly useful for wrappers
+/// (such as [`KeyedBlock`](keyed::KeyedBlock)) that are generic over the width of the block.
+/// See the inherent methods of each variant for the detailed semantics.
+pub trait FixedBlock: Default {
+    /// Type of the elements stored in the block.
+    type Item;
+
+    /// The same block variant, but storing elements of type `U` instead.
+    type Rebind<U>: FixedBlock<Item = U>;
+
+    /// Maximum capacity of the fixed-size block.
+    const CAPACITY: u32;
+
+    /// Checks whether the item at the `index` is vacant (i.e. contains `None`).
+    fn is_vacant(&self, index: usize) -> bool;
+
+    /// Returns the number of non-null elements in the block.
+    fn len(&self) -> u32;
+
+    /// Returns `true` if the block contains zero elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Attempts to retrieve a shared reference to the element at `index`.
+    fn get(&self, index: usize) -> Option<&Self::Item>;
+
+    /// Attempts to retrieve an exclusive reference to the element at `index`.
+    fn get_mut(&mut self, index: usize) -> Option<&mut Self::Item>;
+
+    /// Inserts the `val` at the `index`, returning the old value (if any).
+    fn insert(&mut self, index: usize, val: Self::Item) -> Option<Self::Item>;
+
+    /// Removes the value at the `index`, returning it (if any).
+    fn remove(&mut self, index: usize) -> Option<Self::Item>;
+}
+
+/// Error returned by `try_insert` when the targeted slot is already occupied. The
+/// occupied slot is left untouched, and the rejected value is given back.
+#[derive(Debug, PartialEq, Eq)]
+pub struct OccupiedError<'a, T> {
+    /// Index of the occupied slot.
+    pub index: usize,
+    /// The value that already occupies the slot.
+    pub existing: &'a mut T,
+    /// The value that was rejected.
+    pub value: T,
+}
+
+impl<T> core::fmt::Display for OccupiedError<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "slot {} is already occupied", self.index)
+    }
+}
+
+/// Error returned by the `checked_*` accessors when the index is out of bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOutOfRange {
+    /// The offending index.
+    pub index: usize,
+    /// Maximum capacity of the block.
+    pub capacity: u32,
+}
+
+impl core::fmt::Display for IndexOutOfRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "index {} is out of range for a block of capacity {}", self.index, self.capacity)
+    }
+}
+
+pub use exact::Block;
+
 macro_rules! impl_blocked_optional {
-    ($(#[$attrs:meta])* $name:ident $into_iter:ident $iter:ident $int:ty) => {
+    ($(#[$attrs:meta])* $name:ident $into_iter:ident $iter:ident $slots_mut:ident $drain:ident $extract_if:ident $indices:ident $entries:ident $entries_mut:ident $into_entries:ident $int:ty) => {
         $(#[$attrs])*
-        #[derive(Debug)]
+        ///
+        /// # Layout
+        /// The block is guaranteed to be `#[repr(C)]`: an array of `CAPACITY` slots of `T` comes
+        /// first, followed by the mask (where bit `i` marks the slot at index `i` as occupied) and
+        /// then any trailing padding. Vacant slots are uninitialized. See [`as_ptr`](Self::as_ptr)
+        /// and [`mask_ptr`](Self::mask_ptr) for sharing the storage with foreign code.
+        #[repr(C)]
         pub struct $name<T> {
             data: [MaybeUninit<T>; <$int>::BITS as usize],
             mask: $int,
@@ -22,9 +145,7 @@ macro_rules! impl_blocked_optional {
         /// (i.e. initialized) contents ourselves.
         impl<T> Drop for $name<T> {
             fn drop(&mut self) {
-                for i in 0..Self::CAPACITY as usize {
-                    self.remove(i); // No memory leaks!
-                }
+                self.clear(); // No memory leaks!
             }
         }
 
@@ -49,6 +170,91 @@ macro_rules! impl_blocked_optional {
             }
         }
 
+        /// Only the occupied slots are formatted (as a map from index to value).
+        impl<T: core::fmt::Debug> core::fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(concat!(stringify!($name), " "))?;
+                f.debug_map().entries(self.iter_entries()).finish()
+            }
+        }
+
+        /// Two blocks are equal if they have the same occupancy and their occupied slots
+        /// compare equal. Vacant slots are never read.
+        impl<T: PartialEq> PartialEq for $name<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.mask == other.mask && self.iter().eq(other.iter())
+            }
+        }
+
+        impl<T: Eq> Eq for $name<T> {}
+
+        /// Blocks are ordered lexicographically as if they were `[Option<T>; CAPACITY]` arrays
+        /// (i.e. vacant slots compare less than occupied ones). Vacant slots are never read.
+        impl<T: PartialOrd> PartialOrd for $name<T> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                let slots = 0..Self::CAPACITY as usize;
+                slots.clone().map(|idx| self.get(idx)).partial_cmp(slots.map(|idx| other.get(idx)))
+            }
+        }
+
+        impl<T: Ord> Ord for $name<T> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                let slots = 0..Self::CAPACITY as usize;
+                slots.clone().map(|idx| self.get(idx)).cmp(slots.map(|idx| other.get(idx)))
+            }
+        }
+
+        /// Union of the occupancy, which keeps the value of the left-hand side whenever both
+        /// blocks are occupied (dropping the value of the right-hand side). See [`merge`](Self::merge).
+        impl<T> BitOrAssign for $name<T> {
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.merge(rhs, |_, current, _| current);
+            }
+        }
+
+        /// See the [`BitOrAssign`] implementation.
+        impl<T> BitOr for $name<T> {
+            type Output = Self;
+            fn bitor(mut self, rhs: Self) -> Self {
+                self |= rhs;
+                self
+            }
+        }
+
+        /// Intersection of the occupancy, which drops the values whose indices are vacant in the
+        /// right-hand side. See [`intersect_with`](Self::intersect_with).
+        impl<T, U> BitAndAssign<&$name<U>> for $name<T> {
+            fn bitand_assign(&mut self, rhs: &$name<U>) {
+                self.intersect_with(rhs);
+            }
+        }
+
+        /// See the [`BitAndAssign`] implementation.
+        impl<T, U> BitAnd<&$name<U>> for $name<T> {
+            type Output = Self;
+            fn bitand(mut self, rhs: &$name<U>) -> Self {
+                self &= rhs;
+                self
+            }
+        }
+
+        /// Difference of the occupancy, which drops the values whose indices are occupied in the
+        /// right-hand side. See [`subtract`](Self::subtract).
+        impl<T, U> SubAssign<&$name<U>> for $name<T> {
+            fn sub_assign(&mut self, rhs: &$name<U>) {
+                self.subtract(rhs);
+            }
+        }
+
+        /// See the [`SubAssign`] implementation.
+        impl<T, U> Sub<&$name<U>> for $name<T> {
+            type Output = Self;
+            fn sub(mut self, rhs: &$name<U>) -> Self {
+                self -= rhs;
+                self
+            }
+        }
+
         impl<T> Default for $name<T> {
             fn default() -> Self {
                 let block = MaybeUninit::<[MaybeUninit<T>; <$int>::BITS as usize]>::uninit();
@@ -66,10 +272,7 @@ macro_rules! impl_blocked_optional {
         /// Create a fully initialized direct-access table.
         impl<T> From<[T; <$int>::BITS as usize]> for $name<T> {
             fn from(vals: [T; <$int>::BITS as usize]) -> Self {
-                Self {
-                    data: vals.map(MaybeUninit::new),
-                    mask: <$int>::MAX,
-                }
+                Self::from_array(vals)
             }
         }
 
@@ -108,10 +311,8 @@ macro_rules! impl_blocked_optional {
             type Item = T;
             type IntoIter = iter::$into_iter<T>;
             fn into_iter(self) -> Self::IntoIter {
-                Self::IntoIter {
-                    block: self,
-                    index: 0..Self::CAPACITY as usize,
-                }
+                let (data, mask) = self.into_raw_parts();
+                Self::IntoIter { data, mask }
             }
         }
 
@@ -119,10 +320,7 @@ macro_rules! impl_blocked_optional {
             type Item = &'a T;
             type IntoIter = iter::$iter<'a, T>;
             fn into_iter(self) -> Self::IntoIter {
-                Self::IntoIter {
-                    block: self,
-                    index: 0..$name::<T>::CAPACITY as usize,
-                }
+                Self::IntoIter { data: &self.data, mask: self.mask }
             }
         }
 
@@ -130,15 +328,279 @@ macro_rules! impl_blocked_optional {
             /// Maximum capacity of the fixed-size block.
             pub const CAPACITY: u32 = <$int>::BITS;
 
+            /// Disassembles the block into its raw slots and occupancy mask (where bit `i` marks
+            /// the slot at index `i` as occupied) without running its `Drop` implementation. The
+            /// caller becomes responsible for dropping the occupied slots, e.g. by reassembling the
+            /// block via [`from_raw_parts`](Self::from_raw_parts). Otherwise, the values are leaked.
+            pub fn into_raw_parts(self) -> ([MaybeUninit<T>; <$int>::BITS as usize], $int) {
+                let block = ManuallyDrop::new(self);
+                // SAFETY: The block is never dropped, so ownership of the slots is simply moved out.
+                let data = unsafe { core::ptr::read(&block.data) };
+                (data, block.mask)
+            }
+
+            /// Assembles a block from its raw slots and occupancy mask, where bit `i` of the `mask`
+            /// marks the slot at index `i` as occupied. The block takes ownership of exactly the
+            /// occupied slots: these are dropped along with the block, whereas the rest are never read.
+            ///
+            /// # Safety
+            /// Every slot whose bit is set in the `mask` must be initialized.
+            pub const unsafe fn from_raw_parts(data: [MaybeUninit<T>; <$int>::BITS as usize], mask: $int) -> Self {
+                Self { data, mask }
+            }
+
+            /// Returns a raw pointer to the first slot. Slot `i` lives at `as_ptr().add(i)`, but
+            /// it may only be read if bit `i` of the mask is set (i.e. the slot is occupied).
+            pub const fn as_ptr(&self) -> *const T {
+                self.data.as_ptr().cast()
+            }
+
+            /// Returns a raw mutable pointer to the first slot. Writing to a slot through this
+            /// pointer does not mark it as occupied (nor drop the previous value).
+            pub fn as_mut_ptr(&mut self) -> *mut T {
+                self.data.as_mut_ptr().cast()
+            }
+
+            /// Returns a raw pointer to the occupancy mask, which trails the slots in memory.
+            pub const fn mask_ptr(&self) -> *const $int {
+                &self.mask
+            }
+
             /// Checks whether the item at the `index` is vacant (i.e. contains `None`).
             ///
             /// # Panic
             /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
             pub const fn is_vacant(&self, index: usize) -> bool {
-                assert!(index < Self::CAPACITY as usize);
+                check_index!(index < Self::CAPACITY as usize);
                 self.mask & (1 << index) == 0
             }
 
+            /// Returns a shared reference to the raw storage of the slot at `index`.
+            /// With the `unchecked-index` feature, the bounds check is elided in
+            /// release builds.
+            const fn slot(&self, index: usize) -> &MaybeUninit<T> {
+                #[cfg(feature = "unchecked-index")]
+                {
+                    debug_assert!(index < Self::CAPACITY as usize);
+                    // SAFETY: The `unchecked-index` feature requires the caller to uphold
+                    // that `index < CAPACITY`, which is still validated in debug builds.
+                    unsafe { &*self.data.as_ptr().add(index) }
+                }
+                #[cfg(not(feature = "unchecked-index"))]
+                {
+                    &self.data[index]
+                }
+            }
+
+            /// Returns an exclusive reference to the raw storage of the slot at `index`.
+            /// With the `unchecked-index` feature, the bounds check is elided in
+            /// release builds.
+            const fn slot_mut(&mut self, index: usize) -> &mut MaybeUninit<T> {
+                #[cfg(feature = "unchecked-index")]
+                {
+                    debug_assert!(index < Self::CAPACITY as usize);
+                    // SAFETY: The `unchecked-index` feature requires the caller to uphold
+                    // that `index < CAPACITY`, which is still validated in debug builds.
+                    unsafe { &mut *self.data.as_mut_ptr().add(index) }
+                }
+                #[cfg(not(feature = "unchecked-index"))]
+                {
+                    &mut self.data[index]
+                }
+            }
+
+            /// Drops every occupied value and empties the block. Only the set bits of the mask are
+            /// visited, so this is cheap for sparse (and especially empty) blocks.
+            pub fn clear(&mut self) {
+                while self.mask != 0 {
+                    let idx = self.mask.trailing_zeros() as usize;
+                    self.remove(idx);
+                }
+            }
+
+            /// Drops every occupied value at an index `>= n`, leaving the slots in `0..n` untouched.
+            /// Only the set bits of the upper part of the mask are visited. Thus, `truncate(0)` is
+            /// equivalent to [`clear`](Self::clear) while `n >= CAPACITY` is a no-op.
+            pub fn truncate(&mut self, n: usize) {
+                if n >= Self::CAPACITY as usize {
+                    return;
+                }
+
+                while self.mask & <$int>::MAX << n != 0 {
+                    let idx = (self.mask & <$int>::MAX << n).trailing_zeros() as usize;
+                    self.remove(idx);
+                }
+            }
+
+            /// Creates a block from a list of `(index, value)` pairs. Unlike [`from_entries`](Self::from_entries),
+            /// this is usable in `const` contexts for any `T` (not just `T: Copy`) since every value is
+            /// moved into its slot. Of course, the values themselves must still be `const`-constructible.
+            ///
+            /// ```rust
+            /// fn reset() -> u8 { 0 }
+            /// fn tick() -> u8 { 1 }
+            ///
+            #[doc = concat!("static VECTORS: option_block::", stringify!($name), "<fn() -> u8> = option_block::", stringify!($name), "::from_pairs([(0, reset), (2, tick)]);")]
+            /// assert_eq!(VECTORS.get(2).map(|handler| handler()), Some(1));
+            /// assert_eq!(VECTORS.occupied_mask(), 0b101);
+            /// ```
+            ///
+            /// # Panic
+            /// Panics if any index is out of bounds or if any index appears more than once. In a
+            /// `const` context, these are compile-time errors. At run time, all of the values are
+            /// leaked instead of dropped.
+            ///
+            /// ```compile_fail
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<&str> = option_block::", stringify!($name), "::from_pairs([(1, \"a\"), (1, \"b\")]);")]
+            /// ```
+            pub const fn from_pairs<const K: usize>(pairs: [(usize, T); K]) -> Self {
+                let pairs = ManuallyDrop::new(pairs);
+                let base = core::ptr::addr_of!(pairs).cast::<(usize, T)>();
+                let mut data = [const { MaybeUninit::uninit() }; <$int>::BITS as usize];
+                let mut mask: $int = 0;
+
+                let mut i = 0;
+                while i < K {
+                    // SAFETY: `ManuallyDrop` is transparent, so `base` points to the first pair.
+                    let pair = unsafe { base.add(i) };
+                    // SAFETY: `pair` is in bounds and the index is `Copy`, so reading it does not
+                    // move out of the array.
+                    let idx = unsafe { (*pair).0 };
+                    assert!(idx < Self::CAPACITY as usize, "index out of bounds");
+                    assert!(mask & (1 << idx) == 0, "duplicate index");
+
+                    // SAFETY: Each value is moved out exactly once, and the array itself is never dropped.
+                    data[idx] = MaybeUninit::new(unsafe { core::ptr::addr_of!((*pair).1).read() });
+                    mask |= 1 << idx;
+                    i += 1;
+                }
+
+                Self { data, mask }
+            }
+
+            /// Creates a full block from an array of values. Unlike the [`From`] implementation, this
+            /// is usable in `const` contexts for any `T`.
+            ///
+            /// ```rust
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<u8> = option_block::", stringify!($name), "::from_array([3; ", stringify!($int), "::BITS as usize]);")]
+            /// assert!(TABLE.is_full());
+            /// ```
+            pub const fn from_array(vals: [T; <$int>::BITS as usize]) -> Self {
+                let vals = ManuallyDrop::new(vals);
+                // SAFETY: `ManuallyDrop` is transparent and `MaybeUninit<T>` has the same layout as `T`.
+                // Ownership of the values is simply moved since the original array is never dropped.
+                let data = unsafe { core::ptr::addr_of!(vals).cast::<[MaybeUninit<T>; <$int>::BITS as usize]>().read() };
+                Self { data, mask: <$int>::MAX }
+            }
+
+            /// Creates a block from an array of optional values, where slot `i` is occupied if and
+            /// only if `vals[i]` is `Some`. This is usable in `const` contexts for any `T`.
+            ///
+            /// ```rust
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<u8> = option_block::", stringify!($name), "::from_options({")]
+            #[doc = concat!("    let mut vals = [None; ", stringify!($int), "::BITS as usize];")]
+            /// vals[1] = Some(10);
+            /// vals
+            /// });
+            /// assert_eq!(TABLE.get(1), Some(&10));
+            /// assert_eq!(TABLE.len(), 1);
+            /// ```
+            pub const fn from_options(vals: [Option<T>; <$int>::BITS as usize]) -> Self {
+                let vals = ManuallyDrop::new(vals);
+                let base = core::ptr::addr_of!(vals).cast::<Option<T>>();
+                let mut data = [const { MaybeUninit::uninit() }; <$int>::BITS as usize];
+                let mut mask: $int = 0;
+
+                let mut i = 0;
+                while i < Self::CAPACITY as usize {
+                    // SAFETY: `ManuallyDrop` is transparent, so `base` points to the first option.
+                    if let Some(val) = unsafe { &*base.add(i) } {
+                        // SAFETY: Each value is moved out exactly once, and the array itself is
+                        // never dropped.
+                        data[i] = MaybeUninit::new(unsafe { core::ptr::read(val) });
+                        mask |= 1 << i;
+                    }
+                    i += 1;
+                }
+
+                Self { data, mask }
+            }
+
+            /// Creates a block where only the slot at `index` is occupied by `val`. This is usable in
+            /// `const` contexts for any `T`.
+            ///
+            /// ```rust
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<&str> = option_block::", stringify!($name), "::single(3, \"boot\");")]
+            /// assert_eq!(TABLE.get(3), Some(&"boot"));
+            /// assert_eq!(TABLE.len(), 1);
+            /// ```
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. In a `const` context, this is a compile-time error.
+            ///
+            /// ```compile_fail
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<u8> = option_block::", stringify!($name), "::single(", stringify!($int), "::BITS as usize, 0);")]
+            /// ```
+            pub const fn single(index: usize, val: T) -> Self {
+                assert!(index < Self::CAPACITY as usize, "index out of bounds");
+                let mut data = [const { MaybeUninit::uninit() }; <$int>::BITS as usize];
+                data[index] = MaybeUninit::new(val);
+                Self { data, mask: 1 << index }
+            }
+
+            /// Creates a block by invoking `func` for every index in ascending order, much like
+            /// [`core::array::from_fn`]. Only the slots for which `func` returns `Some` are occupied.
+            /// Should `func` panic, the partially built block is dropped.
+            pub fn from_fn(mut func: impl FnMut(usize) -> Option<T>) -> Self {
+                let mut block = Self::default();
+                for idx in 0..Self::CAPACITY as usize {
+                    if let Some(val) = func(idx) {
+                        block.data[idx].write(val);
+                        block.mask |= 1 << idx;
+                    }
+                }
+                block
+            }
+
+            /// Creates a block whose occupancy is exactly the given `mask` by invoking `func` once per
+            /// set bit (in ascending index order). Vacant slots are never touched. Should `func` panic,
+            /// the values constructed so far are dropped.
+            pub fn from_mask_and_fn(mask: $int, func: impl FnMut(usize) -> T) -> Self {
+                let mut block = Self::default();
+                block.fill_vacancies_with_mask(mask, func);
+                block
+            }
+
+            /// Drops every occupied value and then occupies every slot with `func(index)` in ascending
+            /// index order. Should `func` panic, the slots written so far remain occupied while the
+            /// rest are left vacant.
+            pub fn fill_with(&mut self, mut func: impl FnMut(usize) -> T) {
+                self.clear();
+                for idx in 0..Self::CAPACITY as usize {
+                    self.data[idx].write(func(idx));
+                    self.mask |= 1 << idx;
+                }
+            }
+
+            /// Occupies every vacant slot with `func(index)` in ascending index order. The occupied
+            /// slots are never touched, so `func` is invoked exactly [`vacancies`](Self::vacancies)
+            /// times. Should `func` panic, the vacancies written so far remain occupied.
+            pub fn fill_vacancies_with(&mut self, func: impl FnMut(usize) -> T) {
+                self.fill_vacancies_with_mask(<$int>::MAX, func);
+            }
+
+            /// Occupies every vacant slot whose bit is set in the `mask` with `func(index)`.
+            fn fill_vacancies_with_mask(&mut self, mask: $int, mut func: impl FnMut(usize) -> T) {
+                let mut vacant = !self.mask & mask;
+                while vacant != 0 {
+                    let idx = vacant.trailing_zeros() as usize;
+                    vacant &= vacant - 1;
+                    self.data[idx].write(func(idx));
+                    self.mask |= 1 << idx;
+                }
+            }
+
             /// Returns the number of non-null elements in the block.
             pub const fn len(&self) -> u32 {
                 self.mask.count_ones()
@@ -149,6 +611,238 @@ macro_rules! impl_blocked_optional {
                 self.mask == 0
             }
 
+            /// Returns `true` if all slots of the block are occupied.
+            pub const fn is_full(&self) -> bool {
+                self.mask == <$int>::MAX
+            }
+
+            /// Returns the number of vacant slots in the block. This is exactly the number of
+            /// successful lowest-vacancy insertions (i.e. via [`push`](Self::push) or
+            /// [`insert_at_first_vacancy_with`](Self::insert_at_first_vacancy_with)) remaining
+            /// before the block is full.
+            pub const fn vacancies(&self) -> u32 {
+                Self::CAPACITY - self.len()
+            }
+
+            /// Returns `true` if no index is occupied in both this block and `other`.
+            pub const fn is_disjoint<U>(&self, other: &$name<U>) -> bool {
+                self.is_disjoint_mask(other.mask)
+            }
+
+            /// Returns `true` if no occupied index has its bit set in the `mask`.
+            pub const fn is_disjoint_mask(&self, mask: $int) -> bool {
+                self.mask & mask == 0
+            }
+
+            /// Returns `true` if every occupied index is also occupied in `other`.
+            pub const fn is_subset<U>(&self, other: &$name<U>) -> bool {
+                self.is_subset_mask(other.mask)
+            }
+
+            /// Returns `true` if every occupied index has its bit set in the `mask`.
+            pub const fn is_subset_mask(&self, mask: $int) -> bool {
+                self.mask & !mask == 0
+            }
+
+            /// Returns `true` if every index occupied in `other` is also occupied here.
+            pub const fn is_superset<U>(&self, other: &$name<U>) -> bool {
+                self.is_superset_mask(other.mask)
+            }
+
+            /// Returns `true` if every index whose bit is set in the `mask` is occupied.
+            pub const fn is_superset_mask(&self, mask: $int) -> bool {
+                mask & !self.mask == 0
+            }
+
+            /// Returns the lowest vacant index, if any.
+            pub const fn lowest_vacant_index(&self) -> Option<usize> {
+                let vacant = !self.mask;
+                if vacant == 0 {
+                    None
+                } else {
+                    Some(vacant.trailing_zeros() as usize)
+                }
+            }
+
+            /// Returns the lowest vacant index among those whose bits are set in the `allowed` mask.
+            pub const fn lowest_vacant_index_in(&self, allowed: $int) -> Option<usize> {
+                let vacant = !self.mask & allowed;
+                if vacant == 0 {
+                    None
+                } else {
+                    Some(vacant.trailing_zeros() as usize)
+                }
+            }
+
+            /// Returns the highest vacant index among those whose bits are set in the `allowed` mask.
+            pub const fn highest_vacant_index_in(&self, allowed: $int) -> Option<usize> {
+                let vacant = !self.mask & allowed;
+                if vacant == 0 {
+                    None
+                } else {
+                    Some((<$int>::BITS - 1 - vacant.leading_zeros()) as usize)
+                }
+            }
+
+            /// Inserts the `val` at the [lowest vacant index](Self::lowest_vacant_index_in) among
+            /// those whose bits are set in the `allowed` mask, which is then returned. If no allowed
+            /// slot is vacant, the `val` is given back.
+            pub fn insert_at_first_vacancy_in(&mut self, allowed: $int, val: T) -> Result<usize, T> {
+                let Some(idx) = self.lowest_vacant_index_in(allowed) else {
+                    return Err(val);
+                };
+
+                self.mask |= 1 << idx;
+                self.slot_mut(idx).write(val);
+                Ok(idx)
+            }
+
+            /// Same as [`insert_at_first_vacancy_in`](Self::insert_at_first_vacancy_in), except that
+            /// the `val` is inserted at the [highest](Self::highest_vacant_index_in) allowed vacancy.
+            pub fn insert_at_last_vacancy_in(&mut self, allowed: $int, val: T) -> Result<usize, T> {
+                let Some(idx) = self.highest_vacant_index_in(allowed) else {
+                    return Err(val);
+                };
+
+                self.mask |= 1 << idx;
+                self.slot_mut(idx).write(val);
+                Ok(idx)
+            }
+
+            /// Picks a vacant index by invoking `rng` once (if the block is not full) and selecting
+            /// the `n`-th vacancy in ascending order, where `n = rng() % vacancies()`. Thus, every
+            /// vacancy is equally likely given a uniform `rng` (up to the negligible modulo bias).
+            pub fn random_vacant_index(&self, mut rng: impl FnMut() -> u32) -> Option<usize> {
+                let mut vacant = !self.mask;
+                if vacant == 0 {
+                    return None;
+                }
+
+                for _ in 0..rng() % vacant.count_ones() {
+                    vacant &= vacant - 1;
+                }
+
+                Some(vacant.trailing_zeros() as usize)
+            }
+
+            /// Inserts the `val` at a [random vacant index](Self::random_vacant_index), which is then
+            /// returned. If the block is full, `rng` is never invoked and the `val` is given back.
+            pub fn insert_at_random_vacancy(&mut self, val: T, rng: impl FnMut() -> u32) -> Result<usize, T> {
+                let Some(idx) = self.random_vacant_index(rng) else {
+                    return Err(val);
+                };
+
+                self.mask |= 1 << idx;
+                self.slot_mut(idx).write(val);
+                Ok(idx)
+            }
+
+            /// Inserts the `val` at the [lowest vacant index](Self::lowest_vacant_index), which is
+            /// then returned (e.g. as a handle into an object pool). If the block is full, the `val`
+            /// is given back.
+            pub fn push(&mut self, val: T) -> Result<usize, T> {
+                let Some(idx) = self.lowest_vacant_index() else {
+                    return Err(val);
+                };
+
+                self.mask |= 1 << idx;
+                self.slot_mut(idx).write(val);
+                Ok(idx)
+            }
+
+            /// Lazy version of [`push`](Self::push), which only invokes `func` once a vacancy is
+            /// found. The constructed value is written at the lowest vacant index, which is then
+            /// returned. If the block is full, `func` is never invoked and `None` is returned.
+            /// Should `func` panic, the slot is left vacant.
+            pub fn insert_at_first_vacancy_with(&mut self, func: impl FnOnce() -> T) -> Option<usize> {
+                let idx = self.lowest_vacant_index()?;
+                self.slot_mut(idx).write(func());
+                self.mask |= 1 << idx;
+                Some(idx)
+            }
+
+            /// Same as [`insert_at_first_vacancy_with`](Self::insert_at_first_vacancy_with), except
+            /// that the value is written at the highest vacant index instead.
+            pub fn insert_at_last_vacancy_with(&mut self, func: impl FnOnce() -> T) -> Option<usize> {
+                let vacant = !self.mask;
+                if vacant == 0 {
+                    return None;
+                }
+
+                let idx = (<$int>::BITS - 1 - vacant.leading_zeros()) as usize;
+                self.slot_mut(idx).write(func());
+                self.mask |= 1 << idx;
+                Some(idx)
+            }
+
+            /// Inserts the `val` at the vacant index with the highest `score` (ties go to the lowest
+            /// index), which is then returned. The `score` closure is invoked exactly once for every
+            /// vacant index in ascending order. If the block is full, the `val` is given back.
+            pub fn insert_at_vacancy_by(&mut self, mut score: impl FnMut(usize) -> i32, val: T) -> Result<usize, T> {
+                let mut vacant = !self.mask;
+                let mut best = None::<(usize, i32)>;
+
+                while vacant != 0 {
+                    let idx = vacant.trailing_zeros() as usize;
+                    vacant &= vacant - 1;
+
+                    let current = score(idx);
+                    if best.is_none_or(|(_, max)| current > max) {
+                        best = Some((idx, current));
+                    }
+                }
+
+                let Some((idx, _)) = best else {
+                    return Err(val);
+                };
+
+                self.insert(idx, val);
+                Ok(idx)
+            }
+
+            /// Returns the lowest occupied index, if any.
+            pub const fn lowest_occupied_index(&self) -> Option<usize> {
+                if self.mask == 0 {
+                    None
+                } else {
+                    Some(self.mask.trailing_zeros() as usize)
+                }
+            }
+
+            /// Returns the highest occupied index, if any.
+            pub const fn highest_occupied_index(&self) -> Option<usize> {
+                if self.mask == 0 {
+                    None
+                } else {
+                    Some((<$int>::BITS - 1 - self.mask.leading_zeros()) as usize)
+                }
+            }
+
+            /// Removes the entry at the lowest occupied index, returning its index and value.
+            /// Repeated calls thus drain the block in ascending index order.
+            pub fn pop_first(&mut self) -> Option<(usize, T)> {
+                let idx = self.lowest_occupied_index()?;
+                // SAFETY: The lowest set bit of the mask is occupied.
+                Some((idx, unsafe { self.take_unchecked(idx) }))
+            }
+
+            /// Removes the entry at the highest occupied index, returning its index and value.
+            /// Repeated calls thus drain the block in descending index order.
+            pub fn pop_last(&mut self) -> Option<(usize, T)> {
+                let idx = self.highest_occupied_index()?;
+                // SAFETY: The highest set bit of the mask is occupied.
+                Some((idx, unsafe { self.take_unchecked(idx) }))
+            }
+
+            /// Moves the value out of the slot at `index` and marks it as vacant.
+            ///
+            /// # Safety
+            /// The slot at `index` **must** be occupied.
+            unsafe fn take_unchecked(&mut self, index: usize) -> T {
+                self.mask &= !(1 << index);
+                core::mem::replace(self.slot_mut(index), MaybeUninit::uninit()).assume_init()
+            }
+
             /// Returns an immutable reference to the value at `index`.
             /// See the [`get`](Self::get) method for the safe, checked
             /// version of this method.
@@ -157,7 +851,7 @@ macro_rules! impl_blocked_optional {
             /// The queried value **must** be properly initialized. Otherwise,
             /// the behavior is undefined.
             pub const unsafe fn get_unchecked(&self, index: usize) -> &T {
-                self.data[index].assume_init_ref()
+                self.slot(index).assume_init_ref()
             }
 
             /// Attempts to retrieve a shared reference to the element at `index`.
@@ -165,6 +859,7 @@ macro_rules! impl_blocked_optional {
             ///
             /// # Panic
             /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
             pub fn get(&self, index: usize) -> Option<&T> {
                 if self.is_vacant(index) {
                     None
@@ -174,6 +869,35 @@ macro_rules! impl_blocked_optional {
                 }
             }
 
+            /// Non-panicking version of [`get`](Self::get), which also returns `None` if the `index`
+            /// is out of bounds.
+            pub fn checked_get(&self, index: usize) -> Option<&T> {
+                if index < Self::CAPACITY as usize {
+                    self.get(index)
+                } else {
+                    None
+                }
+            }
+
+            /// Non-panicking version of [`get_mut`](Self::get_mut), which also returns `None` if the
+            /// `index` is out of bounds.
+            pub fn checked_get_mut(&mut self, index: usize) -> Option<&mut T> {
+                if index < Self::CAPACITY as usize {
+                    self.get_mut(index)
+                } else {
+                    None
+                }
+            }
+
+            /// Looks up all of the `indices` at once, yielding `None` for every vacant slot. Since
+            /// the returned references are shared, the `indices` may freely repeat.
+            ///
+            /// # Panic
+            /// Panics if any index is out of bounds, just like [`get`](Self::get).
+            pub fn get_many<const K: usize>(&self, indices: [usize; K]) -> [Option<&T>; K] {
+                indices.map(|index| self.get(index))
+            }
+
             /// Returns a mutable reference to the value at `index`.
             /// See the [`get_mut`](Self::get_mut) method for the safe,
             /// checked version of this method.
@@ -182,7 +906,7 @@ macro_rules! impl_blocked_optional {
             /// The queried value **must** be properly initialized. Otherwise,
             /// the behavior is undefined.
             pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
-                self.data[index].assume_init_mut()
+                self.slot_mut(index).assume_init_mut()
             }
 
             /// Attempts to retrieve an exclusive reference to the element at
@@ -190,6 +914,7 @@ macro_rules! impl_blocked_optional {
             ///
             /// # Panic
             /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
             pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
                 if self.is_vacant(index) {
                     None
@@ -199,25 +924,261 @@ macro_rules! impl_blocked_optional {
                 }
             }
 
-            /// If the slot at the given `index` is already occupied, this method returns a mutable
-            /// reference to the inner data. Otherwise, if the slot is vacant, then this method
-            /// inserts the value constructed by `func`. A mutable reference to the inner data is
-            /// nevertheless returned.
-            pub fn get_or_else(&mut self, index: usize, func: impl FnOnce() -> T) -> &mut T {
-                if self.is_vacant(index) {
-                    // SAFETY: Since this slot is initially vacant, then there are no destructors
-                    // that need to be run. It should be impossible to leak resources here.
-                    self.mask |= 1 << index;
-                    self.data[index].write(func())
-                } else {
-                    // SAFETY: We have already verified that the current `index` is not vacant.
-                    unsafe { self.get_unchecked_mut(index) }
-                }
+            /// Applies `func` to the value at `index` only if the slot is occupied. Returns whether
+            /// the slot was occupied (i.e. whether `func` was invoked).
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`, just like [`get_mut`](Self::get_mut).
+            pub fn update(&mut self, index: usize, func: impl FnOnce(&mut T)) -> bool {
+                self.map_occupied(index, func).is_some()
             }
 
-            /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
-            pub fn get_or(&mut self, index: usize, val: T) -> &mut T {
-                self.get_or_else(index, || val)
+            /// Applies `func` to the value at `index` only if the slot is occupied, returning its
+            /// result. Otherwise, returns `None` without invoking `func`.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`, just like [`get_mut`](Self::get_mut).
+            pub fn map_occupied<R>(&mut self, index: usize, func: impl FnOnce(&mut T) -> R) -> Option<R> {
+                self.get_mut(index).map(func)
+            }
+
+            /// Recovers the slot index of a `value` that lives inside this block (e.g. a reference
+            /// obtained from [`get`](Self::get) or [`iter`](Self::iter)). Returns `None` if the
+            /// reference does not point into the storage of this block or if the computed slot is
+            /// vacant. Since all zero-sized values share the same address, this always returns
+            /// `None` for zero-sized types.
+            pub fn index_of_ref(&self, value: &T) -> Option<usize> {
+                let size = core::mem::size_of::<T>();
+                if size == 0 {
+                    return None;
+                }
+
+                let base = self.data.as_ptr().addr();
+                let offset = (value as *const T).addr().checked_sub(base)?;
+                if offset % size != 0 {
+                    return None;
+                }
+
+                let index = offset / size;
+                if index >= Self::CAPACITY as usize || self.is_vacant(index) {
+                    return None;
+                }
+
+                Some(index)
+            }
+
+            /// Splits the block into an exclusive reference to the occupied element at `index` and
+            /// an iterator over shared references to every other occupied element (along with their
+            /// indices). Returns `None` if the slot at `index` is vacant.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn split_one_mut(&mut self, index: usize) -> Option<(&mut T, impl Iterator<Item = (usize, &T)>)> {
+                if self.is_vacant(index) {
+                    return None;
+                }
+
+                let mask = self.mask;
+                let (head, tail) = self.data.split_at_mut(index);
+                let (target, tail) = tail.split_first_mut()?;
+                let (head, tail): (&[_], &[_]) = (head, tail);
+
+                let others = head
+                    .iter()
+                    .enumerate()
+                    .chain(tail.iter().enumerate().map(move |(idx, slot)| (idx + index + 1, slot)))
+                    .filter(move |&(idx, _)| mask & (1 << idx) != 0)
+                    // SAFETY: We have already verified that the current `idx` is not vacant.
+                    .map(|(idx, slot)| (idx, unsafe { slot.assume_init_ref() }));
+
+                // SAFETY: We have already verified that the slot at `index` is not vacant.
+                Some((unsafe { target.assume_init_mut() }, others))
+            }
+
+            /// Moves every occupied entry of `other` into this block. Vacant slots are simply filled,
+            /// whereas `resolve` decides the surviving value (given the index, the current value,
+            /// and the incoming value) whenever both slots are occupied. Should `resolve` panic, the
+            /// conflicting slot is left vacant.
+            pub fn merge(&mut self, other: Self, mut resolve: impl FnMut(usize, T, T) -> T) {
+                for (idx, val) in other.into_entries() {
+                    let val = match self.remove(idx) {
+                        Some(current) => resolve(idx, current, val),
+                        None => val,
+                    };
+                    self.insert(idx, val);
+                }
+            }
+
+            /// Consumes both blocks and keeps the entries whose indices are occupied in exactly one of
+            /// them (i.e. the resulting mask is the XOR of both masks). Values are moved from whichever
+            /// block has them, whereas both values of an index occupied in both blocks are dropped.
+            pub fn symmetric_difference(mut self, other: Self) -> Self {
+                for (idx, val) in other.into_entries() {
+                    if self.remove(idx).is_none() {
+                        self.insert(idx, val);
+                    }
+                }
+                self
+            }
+
+            /// Retains only the entries whose indices are also occupied in `other` (regardless of its
+            /// element type). All other values are dropped.
+            pub fn intersect_with<U>(&mut self, other: &$name<U>) {
+                self.intersect_with_mask(other.mask);
+            }
+
+            /// Retains only the entries whose bits are set in the `mask`. All other values are dropped.
+            pub fn intersect_with_mask(&mut self, mask: $int) {
+                self.remove_masked(self.mask & !mask);
+            }
+
+            /// Removes the entries whose indices are occupied in `other` (regardless of its element
+            /// type). The removed values are dropped, while indices only occupied in `other` are ignored.
+            pub fn subtract<U>(&mut self, other: &$name<U>) {
+                self.subtract_mask(other.mask);
+            }
+
+            /// Removes the entries whose bits are set in the `mask`. The removed values are dropped.
+            pub fn subtract_mask(&mut self, mask: $int) {
+                self.remove_masked(self.mask & mask);
+            }
+
+            /// Removes (and drops) the entries whose bits are set in the `doomed` mask. Only the set
+            /// bits are visited.
+            fn remove_masked(&mut self, mut doomed: $int) {
+                while doomed != 0 {
+                    let idx = doomed.trailing_zeros() as usize;
+                    doomed &= doomed - 1;
+                    self.remove(idx);
+                }
+            }
+
+            /// Consumes both blocks and pairs up their values over the indices occupied in both. The
+            /// values that are only present in one of the blocks are dropped.
+            pub fn zip<U>(self, mut other: $name<U>) -> $name<(T, U)> {
+                let mut block = $name::default();
+                for (idx, val) in self.into_entries() {
+                    if let Some(paired) = other.remove(idx) {
+                        block.data[idx].write((val, paired));
+                        block.mask |= 1 << idx;
+                    }
+                }
+                block
+            }
+
+            /// Joins this block with `other` over the indices occupied in both, yielding shared
+            /// references into both blocks (in ascending index order).
+            pub fn zip_ref<'a, U>(&'a self, other: &'a $name<U>) -> impl Iterator<Item = (usize, &'a T, &'a U)> {
+                let mut mask = self.mask & other.mask;
+                core::iter::from_fn(move || {
+                    if mask == 0 {
+                        return None;
+                    }
+
+                    let idx = mask.trailing_zeros() as usize;
+                    mask &= mask - 1;
+
+                    // SAFETY: We have already verified that both slots at the current `idx` are not vacant.
+                    Some(unsafe { (idx, self.get_unchecked(idx), other.get_unchecked(idx)) })
+                })
+            }
+
+            /// Joins this block with `other` over the indices occupied in both, yielding an exclusive
+            /// reference into `self` alongside a shared reference into `other` (in ascending index
+            /// order). This is useful for combining two blocks in place. Only the set bits of the
+            /// joint mask are visited.
+            pub fn zip_mut<'a, U>(
+                &'a mut self,
+                other: &'a $name<U>,
+            ) -> impl Iterator<Item = (usize, &'a mut T, &'a U)> {
+                let mut mask = self.mask & other.mask;
+                let mut slots = self.data.iter_mut().zip(other.data.iter()).enumerate();
+                core::iter::from_fn(move || {
+                    if mask == 0 {
+                        return None;
+                    }
+                    let target = mask.trailing_zeros() as usize;
+                    mask &= mask - 1;
+
+                    // Jump straight over the slots that are vacant in either block.
+                    let cursor = <$int>::BITS as usize - slots.len();
+                    let (idx, (a, b)) = slots.nth(target - cursor)?;
+
+                    // SAFETY: We have already verified that both slots at the current `idx` are not vacant.
+                    Some(unsafe { (idx, a.assume_init_mut(), b.assume_init_ref()) })
+                })
+            }
+
+            /// Mutates the values of this block in place via `func` using the values of `other` at
+            /// the indices occupied in both (in ascending index order). Neither block is restructured.
+            pub fn apply_pairwise<U>(&mut self, other: &$name<U>, mut func: impl FnMut(usize, &mut T, &U)) {
+                for (idx, val, delta) in self.zip_mut(other) {
+                    func(idx, val, delta);
+                }
+            }
+
+            /// Same as [`apply_pairwise`](Self::apply_pairwise), except that the indices occupied
+            /// only in `other` are filled with the value constructed by `insert` (in ascending index
+            /// order alongside the pairwise updates).
+            pub fn apply_or_insert_pairwise<U>(
+                &mut self,
+                other: &$name<U>,
+                mut func: impl FnMut(usize, &mut T, &U),
+                mut insert: impl FnMut(usize, &U) -> T,
+            ) {
+                for (idx, delta) in other.iter_entries() {
+                    match self.get_mut(idx) {
+                        Some(val) => func(idx, val, delta),
+                        None => drop(self.insert(idx, insert(idx, delta))),
+                    }
+                }
+            }
+
+            /// Joins this block with `other` over the indices occupied in both, yielding exclusive
+            /// references into both blocks (in ascending index order). See [`zip_mut`](Self::zip_mut)
+            /// for the version which only borrows `other` immutably.
+            pub fn zip_mut_mut<'a, U>(
+                &'a mut self,
+                other: &'a mut $name<U>,
+            ) -> impl Iterator<Item = (usize, &'a mut T, &'a mut U)> {
+                let mut mask = self.mask & other.mask;
+                let mut slots = self.data.iter_mut().zip(other.data.iter_mut()).enumerate();
+                core::iter::from_fn(move || {
+                    if mask == 0 {
+                        return None;
+                    }
+                    let target = mask.trailing_zeros() as usize;
+                    mask &= mask - 1;
+
+                    // Jump straight over the slots that are vacant in either block.
+                    let cursor = <$int>::BITS as usize - slots.len();
+                    let (idx, (a, b)) = slots.nth(target - cursor)?;
+
+                    // SAFETY: We have already verified that both slots at the current `idx` are not vacant.
+                    Some(unsafe { (idx, a.assume_init_mut(), b.assume_init_mut()) })
+                })
+            }
+
+            /// If the slot at the given `index` is already occupied, this method returns a mutable
+            /// reference to the inner data. Otherwise, if the slot is vacant, then this method
+            /// inserts the value constructed by `func`. A mutable reference to the inner data is
+            /// nevertheless returned.
+            pub fn get_or_else(&mut self, index: usize, func: impl FnOnce() -> T) -> &mut T {
+                if self.is_vacant(index) {
+                    // SAFETY: Since this slot is initially vacant, then there are no destructors
+                    // that need to be run. It should be impossible to leak resources here.
+                    self.mask |= 1 << index;
+                    self.slot_mut(index).write(func())
+                } else {
+                    // SAFETY: We have already verified that the current `index` is not vacant.
+                    unsafe { self.get_unchecked_mut(index) }
+                }
+            }
+
+            /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
+            pub fn get_or(&mut self, index: usize, val: T) -> &mut T {
+                self.get_or_else(index, || val)
             }
 
             /// Inserts the `val` at the `index`. If a value already exists, it returns `Some`
@@ -225,9 +1186,10 @@ macro_rules! impl_blocked_optional {
             ///
             /// # Panic
             /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
-            pub fn insert(&mut self, index: usize, val: T) -> Option<T> {
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub const fn insert(&mut self, index: usize, val: T) -> Option<T> {
                 let vacant = self.is_vacant(index);
-                let uninit_val = core::mem::replace(&mut self.data[index], MaybeUninit::new(val));
+                let uninit_val = core::mem::replace(self.slot_mut(index), MaybeUninit::new(val));
                 self.mask |= 1 << index;
 
                 if vacant {
@@ -239,142 +1201,1380 @@ macro_rules! impl_blocked_optional {
                 }
             }
 
+            /// Inserts the `val` at the `index` only if the slot is vacant, returning a mutable
+            /// reference to the newly inserted value. Otherwise, the occupied slot is left untouched
+            /// and the [error](OccupiedError) gives back the rejected `val`.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn try_insert(&mut self, index: usize, val: T) -> Result<&mut T, OccupiedError<'_, T>> {
+                if self.is_vacant(index) {
+                    self.mask |= 1 << index;
+                    Ok(self.slot_mut(index).write(val))
+                } else {
+                    // SAFETY: We have already verified that the current `index` is not vacant.
+                    let existing = unsafe { self.get_unchecked_mut(index) };
+                    Err(OccupiedError { index, existing, value: val })
+                }
+            }
+
+            /// Swaps the `val` into the slot at `index` only if it is occupied, returning the old
+            /// value. Otherwise, the block is left untouched and the `val` is given back. This is the
+            /// counterpart of [`try_insert`](Self::try_insert), so the mask is never modified.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn replace(&mut self, index: usize, val: T) -> Result<T, T> {
+                match self.get_mut(index) {
+                    Some(slot) => Ok(core::mem::replace(slot, val)),
+                    None => Err(val),
+                }
+            }
+
+            /// Lazy version of [`insert`](Self::insert), which only invokes `func` after validating
+            /// the `index`. The displaced value (if any) is moved out of the slot first so that the
+            /// result of `func` is written directly into the slot's storage, without an intermediate
+            /// move of the (potentially large) `T`. The displaced value is then returned. Should `func`
+            /// panic, the slot is left vacant and the displaced value is dropped.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn insert_with(&mut self, index: usize, func: impl FnOnce() -> T) -> Option<T> {
+                // NOTE: The slot is vacated (which also validates the `index`) before invoking
+                // `func` so that the mask never claims an unwritten slot in case of a panic.
+                let old = self.remove(index);
+                self.slot_mut(index).write(func());
+                self.mask |= 1 << index;
+                old
+            }
+
+            /// Non-panicking version of [`insert`](Self::insert). If the `index` is out of bounds, the
+            /// block is left untouched and the `val` is given back along with the error.
+            pub fn checked_insert(&mut self, index: usize, val: T) -> Result<Option<T>, (IndexOutOfRange, T)> {
+                match self.check_range(index) {
+                    Ok(()) => Ok(self.insert(index, val)),
+                    Err(err) => Err((err, val)),
+                }
+            }
+
+            /// Inserts each of the `entries` in order (like [`insert`](Self::insert)), returning the
+            /// displaced values in the same positions. Should an index repeat, the later entry
+            /// displaces the earlier one.
+            ///
+            /// # Panic
+            /// Panics if any index is out of bounds, just like [`insert`](Self::insert).
+            pub fn insert_many<const K: usize>(&mut self, entries: [(usize, T); K]) -> [Option<T>; K] {
+                entries.map(|(index, val)| self.insert(index, val))
+            }
+
+            /// Inserts all of the `entries` only if every index is in bounds, currently vacant, and
+            /// distinct from the others. Otherwise, nothing is written and the `entries` are given
+            /// back as is.
+            pub fn try_insert_many<const K: usize>(&mut self, entries: [(usize, T); K]) -> Result<(), [(usize, T); K]> {
+                let mut claimed: $int = 0;
+                for &(index, _) in &entries {
+                    if index >= Self::CAPACITY as usize {
+                        return Err(entries);
+                    }
+
+                    let bit = 1 << index;
+                    if (self.mask | claimed) & bit != 0 {
+                        return Err(entries);
+                    }
+
+                    claimed |= bit;
+                }
+
+                for (index, val) in entries {
+                    self.slot_mut(index).write(val);
+                }
+
+                self.mask |= claimed;
+                Ok(())
+            }
+
             /// Removes the value at the `index`. If a value already exists, it returns `Some`
             /// containing that value. Otherwise, it returns `None`.
             ///
             /// # Panic
             /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
-            pub fn remove(&mut self, index: usize) -> Option<T> {
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub const fn remove(&mut self, index: usize) -> Option<T> {
                 if self.is_vacant(index) {
                     return None;
                 }
 
-                let uninit_val = core::mem::replace(&mut self.data[index], MaybeUninit::uninit());
+                let uninit_val = core::mem::replace(self.slot_mut(index), MaybeUninit::uninit());
                 self.mask &= !(1 << index);
 
                 // SAFETY: We have already verified that the current `index` is not vacant.
                 Some(unsafe { uninit_val.assume_init() })
             }
 
-            /// Create a by-reference iterator for this block.
-            pub fn iter(&self) -> iter::$iter<T> {
-                iter::$iter {
-                    block: self,
-                    index: 0..Self::CAPACITY as usize,
-                }
+            /// Non-panicking version of [`remove`](Self::remove). If the `index` is out of bounds, the
+            /// block is left untouched.
+            pub fn checked_remove(&mut self, index: usize) -> Result<Option<T>, IndexOutOfRange> {
+                self.check_range(index)?;
+                Ok(self.remove(index))
             }
-        }
 
-        impl<T: Default> $name<T> {
-            /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
-            pub fn get_or_default(&mut self, index: usize) -> &mut T {
-                self.get_or_else(index, Default::default)
+            /// Validates the `index` against the capacity without panicking.
+            const fn check_range(&self, index: usize) -> Result<(), IndexOutOfRange> {
+                if index < Self::CAPACITY as usize {
+                    Ok(())
+                } else {
+                    Err(IndexOutOfRange { index, capacity: Self::CAPACITY })
+                }
             }
-        }
-    };
-}
 
-impl_blocked_optional! {
-    /// A fixed block of optionals masked by a [`u8`](u8),
-    /// which may thus contain at most 8 elements.
-    Block8 Block8IntoIter Block8Iter u8
-}
+            /// Builder version of [`insert`](Self::insert), which drops the displaced value (if any).
+            /// See [`from_pairs`](Self::from_pairs) for building blocks in `const` contexts.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn with(mut self, index: usize, val: T) -> Self {
+                self.insert(index, val);
+                self
+            }
 
-impl_blocked_optional! {
-    /// A fixed block of optionals masked by a [`u16`](u16),
-    /// which may thus contain at most 16 elements.
-    Block16 Block16IntoIter Block16Iter u16
-}
+            /// Builder version of [`remove`](Self::remove), which drops the removed value (if any).
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn without(mut self, index: usize) -> Self {
+                self.remove(index);
+                self
+            }
 
-impl_blocked_optional! {
-    /// A fixed block of optionals masked by a [`u32`](u32),
-    /// which may thus contain at most 32 elements.
-    Block32 Block32IntoIter Block32Iter u32
-}
+            /// Removes the value at each of the `indices` in order, returning the old values in
+            /// the same positions. Repeated indices yield `None` after their first occurrence.
+            ///
+            /// # Panic
+            /// Panics if any index is out of bounds, just like [`remove`](Self::remove). All values
+            /// removed prior to the panic are dropped.
+            pub fn remove_many<const K: usize>(&mut self, indices: [usize; K]) -> [Option<T>; K] {
+                indices.map(|index| self.remove(index))
+            }
 
-impl_blocked_optional! {
-    /// A fixed block of optionals masked by a [`u64`](u64),
-    /// which may thus contain at most 64 elements.
-    Block64 Block64IntoIter Block64Iter u64
-}
+            /// Replays a stream of [change records](diff::ChangeOwned) onto this block in order.
+            /// On success, returns the number of applied changes. Otherwise, application stops
+            /// at the first change that does not match the current state of the block (e.g.
+            /// removing a vacant slot or an out-of-bounds index), which is then returned in the
+            /// [error](diff::DiffMismatch). Note that all prior changes remain applied.
+            pub fn apply_diff<I>(&mut self, changes: I) -> Result<usize, diff::DiffMismatch<T>>
+            where
+                I: IntoIterator<Item = diff::ChangeOwned<T>>,
+            {
+                use diff::ChangeOwned;
+                let mut applied = 0;
+                for change in changes {
+                    let index = change.index();
+                    let is_valid = index < Self::CAPACITY as usize
+                        && match change {
+                            ChangeOwned::Added(..) => self.is_vacant(index),
+                            ChangeOwned::Removed(..) | ChangeOwned::Modified(..) => !self.is_vacant(index),
+                        };
 
-impl_blocked_optional! {
-    /// A fixed block of optionals masked by a [`u128`](u128),
-    /// which may thus contain at most 128 elements.
-    Block128 Block128IntoIter Block128Iter u128
-}
+                    if !is_valid {
+                        return Err(diff::DiffMismatch { applied, change });
+                    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+                    match change {
+                        ChangeOwned::Added(idx, val) | ChangeOwned::Modified(idx, val) => drop(self.insert(idx, val)),
+                        ChangeOwned::Removed(idx) => drop(self.remove(idx)),
+                    }
 
-    #[test]
-    fn capacity_tests() {
-        assert_eq!(Block8::<()>::CAPACITY, 8);
-        assert_eq!(Block16::<()>::CAPACITY, 16);
-        assert_eq!(Block32::<()>::CAPACITY, 32);
-        assert_eq!(Block64::<()>::CAPACITY, 64);
-        assert_eq!(Block128::<()>::CAPACITY, 128);
-    }
+                    applied += 1;
+                }
 
-    #[test]
-    fn size_tests() {
-        use core::mem::size_of;
-        assert_eq!(size_of::<Block8<u8>>(), 8 + 1);
-        assert_eq!(size_of::<Block16<u8>>(), 16 + 2);
-        assert_eq!(size_of::<Block32<u8>>(), 32 + 4);
-        assert_eq!(size_of::<Block64<u8>>(), 64 + 8);
-        assert_eq!(size_of::<Block128<u8>>(), 128 + 16);
-    }
+                Ok(applied)
+            }
 
-    #[test]
-    fn insert_replace_semantics() {
-        let mut block = Block8::default();
-        assert!(block.is_empty());
+            /// Stores `Some` value at the `index` (like [`insert`](Self::insert)) or vacates the slot
+            /// when given `None` (like [`remove`](Self::remove)). In either case, the previous contents
+            /// of the slot are returned.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub const fn set(&mut self, index: usize, val: Option<T>) -> Option<T> {
+                if val.is_some() {
+                    self.insert(index, val.unwrap())
+                } else {
+                    // NOTE: Forgetting a `None` is a no-op. This only convinces
+                    // the compiler that no destructor runs in a `const` context.
+                    core::mem::forget(val);
+                    self.remove(index)
+                }
+            }
 
-        assert!(block.insert(0, 32).is_none());
-        assert!(block.insert(1, 64).is_none());
+            /// Transforms every occupied value in place by moving it into `func` (along with its index)
+            /// and writing the result back into the same slot, in ascending index order. If `func`
+            /// panics, the slot currently being processed is left vacant while all other entries
+            /// survive.
+            pub fn map_in_place(&mut self, mut func: impl FnMut(usize, T) -> T) {
+                for idx in 0..Self::CAPACITY as usize {
+                    // NOTE: The slot is vacated for the duration of the call so
+                    // that a panic never leaves behind a moved-out value.
+                    if let Some(val) = self.remove(idx) {
+                        self.insert(idx, func(idx, val));
+                    }
+                }
+            }
 
-        assert_eq!(block.insert(0, 1), Some(32));
-        assert_eq!(block.insert(1, 2), Some(64));
+            /// Moves every occupied entry into the same slot of `dest`, which may be any (typically
+            /// wider) block variant. This is all-or-nothing: if any occupied index is out of bounds
+            /// for `dest` or collides with an occupied slot of `dest`, then nothing is moved and this
+            /// block is handed back in the `Err` variant.
+            pub fn move_into<B: FixedBlock<Item = T>>(mut self, dest: &mut B) -> Result<(), Self> {
+                let conflict = (0..Self::CAPACITY as usize)
+                    .filter(|&idx| !self.is_vacant(idx))
+                    .any(|idx| idx >= B::CAPACITY as usize || !dest.is_vacant(idx));
 
-        assert_eq!(block.remove(0), Some(1));
-        assert_eq!(block.remove(1), Some(2));
+                if conflict {
+                    return Err(self);
+                }
 
-        assert!(block.is_empty());
-    }
+                for idx in 0..Self::CAPACITY as usize {
+                    if let Some(val) = self.remove(idx) {
+                        dest.insert(idx, val);
+                    }
+                }
 
-    #[test]
-    fn check_iterators() {
-        let block = Block8::<usize>::from([0, 1, 2, 3, 4, 5, 6, 7]);
+                Ok(())
+            }
 
-        for (idx, &val) in block.iter().enumerate() {
-            assert_eq!(idx, val);
-        }
+            /// Consumes the block and transforms every occupied value via `func` (in ascending index
+            /// order) into a new block with the identical occupancy. Should `func` panic, both the
+            /// already mapped values and the not-yet-mapped ones are dropped.
+            pub fn map<U>(self, mut func: impl FnMut(T) -> U) -> $name<U> {
+                match self.try_map(|_, val| Ok::<_, core::convert::Infallible>(func(val))) {
+                    Ok(block) => block,
+                    Err((_, never)) => match never {},
+                }
+            }
 
-        for (idx, val) in block.into_iter().enumerate() {
-            assert_eq!(idx, val);
-        }
-    }
+            /// Consumes the block and fallibly transforms every occupied value via `func` (along
+            /// with its index, in ascending index order) into a new block with the identical
+            /// occupancy. The first failure aborts the transformation, in which case the failing
+            /// index and its error are returned. All remaining (mapped and unmapped) values are
+            /// dropped, even if `func` panics.
+            pub fn try_map<U, E>(self, mut func: impl FnMut(usize, T) -> Result<U, E>) -> Result<$name<U>, (usize, E)> {
+                let mut block = $name::default();
+                for (idx, val) in self.into_entries() {
+                    block.data[idx].write(func(idx, val).map_err(|err| (idx, err))?);
+                    block.mask |= 1 << idx;
+                }
+                Ok(block)
+            }
 
-    #[test]
-    fn indexing_operations() {
-        use core::ops::Range;
-        type Block = Block8<usize>;
-        const RANGE: Range<usize> = 0..Block::CAPACITY as usize;
-        let mut block = Block::from([0, 1, 2, 3, 4, 5, 6, 7]);
+            /// Consumes the block and transforms every occupied value via `func` (along with its
+            /// index, in ascending index order) into a new block. A slot of the new block is only
+            /// occupied if `func` returns `Some` for it. Otherwise, the value is simply dropped.
+            pub fn filter_map<U>(self, mut func: impl FnMut(usize, T) -> Option<U>) -> $name<U> {
+                let mut block = $name::default();
+                for (idx, val) in self.into_entries() {
+                    if let Some(val) = func(idx, val) {
+                        block.data[idx].write(val);
+                        block.mask |= 1 << idx;
+                    }
+                }
+                block
+            }
 
-        for i in RANGE {
-            assert_eq!(block[i], i);
-        }
+            /// Converts every occupied value into another type via [`From`], preserving occupancy.
+            /// This is the container-level lifting of `From`, which cannot be expressed as a
+            /// blanket trait implementation due to coherence.
+            pub fn convert<U: From<T>>(self) -> $name<U> {
+                match self.try_convert() {
+                    Ok(block) => block,
+                    Err((_, never)) => match never {},
+                }
+            }
 
-        for i in RANGE {
-            block[i] *= 2;
-        }
+            /// Fallibly converts every occupied value into another type via [`TryFrom`],
+            /// preserving occupancy. Values are converted in ascending index order. The first
+            /// failure aborts the conversion, in which case the failing index and its error are
+            /// returned. All remaining (converted and unconverted) values are dropped.
+            pub fn try_convert<U: TryFrom<T>>(mut self) -> Result<$name<U>, (usize, U::Error)> {
+                let mut block = $name::default();
+                for idx in 0..Self::CAPACITY as usize {
+                    let Some(val) = self.remove(idx) else {
+                        continue;
+                    };
+                    let val = U::try_from(val).map_err(|err| (idx, err))?;
+                    block.insert(idx, val);
+                }
+                Ok(block)
+            }
+
+            /// Returns `true` if the occupied slots form a dense prefix (i.e. `0..len()`).
+            const fn is_dense_prefix(&self) -> bool {
+                self.mask & self.mask.wrapping_add(1) == 0
+            }
+
+            /// Resizes the dense prefix of occupied slots to `len`, much like [`Vec::resize_with`].
+            /// If `len` is greater than the current length, the vacancies in `self.len()..len` are
+            /// filled by calling `func`. Otherwise, the entries in `len..self.len()` are dropped.
+            ///
+            /// # Panic
+            /// Panics if the occupied slots do not form a dense prefix (i.e. there is a vacancy
+            /// before the last occupied slot) or if `len > CAPACITY`.
+            ///
+            /// [`Vec::resize_with`]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.resize_with
+            pub fn resize_with(&mut self, len: usize, mut func: impl FnMut() -> T) {
+                assert!(self.is_dense_prefix(), "occupied slots must form a dense prefix");
+                assert!(len <= Self::CAPACITY as usize);
+
+                let current = self.len() as usize;
+                for idx in current..len {
+                    self.insert(idx, func());
+                }
+                for idx in len..current {
+                    self.remove(idx);
+                }
+            }
+
+            /// Moves every occupied value down into the lowest vacancies while preserving their
+            /// relative order, so that the occupied slots form a dense prefix (i.e. `0..len()`).
+            /// The values are moved, not cloned.
+            pub fn compact(&mut self) {
+                self.compact_with(|_, _| ());
+            }
+
+            /// Same as [`compact`](Self::compact), except that `func` is invoked with the old and
+            /// new index of every value that has been moved (e.g. for fixing up external handles).
+            /// Should `func` panic, every value remains tracked at its current slot.
+            pub fn compact_with(&mut self, mut func: impl FnMut(usize, usize)) {
+                let mut remaining = self.mask;
+                let mut target = 0;
+                while remaining != 0 {
+                    let idx = remaining.trailing_zeros() as usize;
+                    remaining &= remaining - 1;
+
+                    if idx != target {
+                        // NOTE: The target slot is vacant, so swapping simply moves the value.
+                        self.data.swap(idx, target);
+                        self.mask = self.mask & !(1 << idx) | 1 << target;
+                        func(idx, target);
+                    }
+
+                    target += 1;
+                }
+            }
+
+            /// Returns the occupied slots as a slice if they already form a dense prefix (i.e. `0..len()`).
+            /// Otherwise, returns `None`. Unlike [`make_contiguous`](Self::make_contiguous), no value
+            /// is ever moved.
+            pub fn as_contiguous_slice(&self) -> Option<&[T]> {
+                if !self.is_dense_prefix() {
+                    return None;
+                }
+
+                // SAFETY: The first `len` slots are occupied (and thus initialized). Since
+                // `MaybeUninit<T>` has the same layout as `T`, the cast is valid.
+                Some(unsafe { core::slice::from_raw_parts(self.data.as_ptr().cast::<T>(), self.len() as usize) })
+            }
+
+            /// Mutable version of [`as_contiguous_slice`](Self::as_contiguous_slice).
+            pub fn as_contiguous_slice_mut(&mut self) -> Option<&mut [T]> {
+                if !self.is_dense_prefix() {
+                    return None;
+                }
+
+                let len = self.len() as usize;
+                // SAFETY: Same as above. The slice also borrows the block mutably, so the mask
+                // cannot be modified in the meantime.
+                Some(unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr().cast::<T>(), len) })
+            }
+
+            /// [Compacts](Self::compact) the block if necessary and then returns the occupied slots
+            /// (i.e. the dense prefix `0..len()`) as a mutable slice, much like [`VecDeque::make_contiguous`].
+            ///
+            /// [`VecDeque::make_contiguous`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html#method.make_contiguous
+            pub fn make_contiguous(&mut self) -> &mut [T] {
+                if !self.is_dense_prefix() {
+                    self.compact();
+                }
+
+                let len = self.len() as usize;
+                // SAFETY: The first `len` slots are occupied (and thus initialized) after compaction.
+                // Since `MaybeUninit<T>` has the same layout as `T`, the cast is valid. The slice also
+                // borrows the block mutably, so the mask cannot be modified in the meantime.
+                unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr().cast::<T>(), len) }
+            }
+
+            /// Rotates the slots in-place such that the entry at index `n` moves to index `0`
+            /// (i.e. every entry moves `n` slots toward index `0`, wrapping around), much like
+            /// [`slice::rotate_left`]. Values are moved, never cloned nor dropped.
+            pub fn rotate_left(&mut self, n: u32) {
+                let n = n % Self::CAPACITY;
+                // NOTE: Moving toward index `0` corresponds to rotating the bits toward the LSB.
+                self.data.rotate_left(n as usize);
+                self.mask = self.mask.rotate_right(n);
+            }
+
+            /// Rotates the slots in-place such that every entry moves `n` slots toward the last
+            /// index (wrapping around), much like [`slice::rotate_right`]. Values are moved,
+            /// never cloned nor dropped.
+            pub fn rotate_right(&mut self, n: u32) {
+                let n = n % Self::CAPACITY;
+                self.data.rotate_right(n as usize);
+                self.mask = self.mask.rotate_left(n);
+            }
+
+            /// Inserts the `val` at the `index` after moving every slot in `index..CAPACITY` up by one
+            /// (vacancies included), so that the relative order of the entries is preserved. The
+            /// entry that gets pushed past the last index (if any) is returned.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn shift_insert(&mut self, index: usize, val: T) -> Option<T> {
+                check_index!(index < Self::CAPACITY as usize);
+                let displaced = self.remove(Self::CAPACITY as usize - 1);
+
+                // NOTE: The last slot is vacant at this point, so it simply wraps around to `index`.
+                self.data[index..].rotate_right(1);
+                let upper = <$int>::MAX << index;
+                self.mask = self.mask & !upper | (self.mask & upper) << 1;
+
+                self.mask |= 1 << index;
+                self.slot_mut(index).write(val);
+                displaced
+            }
+
+            /// Removes the value at the `index` (if any) and then moves every slot in `index + 1..CAPACITY`
+            /// down by one (vacancies included), so that the relative order of the entries is preserved.
+            /// The last slot is left vacant.
+            ///
+            /// # Panic
+            /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            /// With the `unchecked-index` feature, this is only checked in debug builds.
+            pub fn shift_remove(&mut self, index: usize) -> Option<T> {
+                let removed = self.remove(index);
+
+                // NOTE: The slot at `index` is vacant at this point, so it simply wraps around to the end.
+                self.data[index..].rotate_left(1);
+                let upper = <$int>::MAX << index;
+                self.mask = self.mask & !upper | (self.mask & upper) >> 1;
+                removed
+            }
+
+            /// Moves every entry `n` slots toward index `0`. The entries in `0..n` fall off (and are
+            /// thus dropped) while the newly exposed slots at the top are left vacant. Shifting by at
+            /// least `CAPACITY` simply [clears](Self::clear) the block.
+            pub fn shift_left(&mut self, n: u32) {
+                if n >= Self::CAPACITY {
+                    self.clear();
+                    return;
+                }
+
+                let mut dropped = self.mask & !(<$int>::MAX << n);
+                while dropped != 0 {
+                    let idx = dropped.trailing_zeros() as usize;
+                    dropped &= dropped - 1;
+                    self.remove(idx);
+                }
+
+                // NOTE: The slots that wrap around are vacant at this point.
+                self.data.rotate_left(n as usize);
+                self.mask >>= n;
+            }
+
+            /// Moves every entry `n` slots toward the last index. The entries in the top `n` slots
+            /// fall off (and are thus dropped) while the newly exposed slots at the bottom are left
+            /// vacant. Shifting by at least `CAPACITY` simply [clears](Self::clear) the block.
+            pub fn shift_right(&mut self, n: u32) {
+                if n >= Self::CAPACITY {
+                    self.clear();
+                    return;
+                }
+
+                let mut dropped = self.mask & !(<$int>::MAX >> n);
+                while dropped != 0 {
+                    let idx = dropped.trailing_zeros() as usize;
+                    dropped &= dropped - 1;
+                    self.remove(idx);
+                }
+
+                // NOTE: The slots that wrap around are vacant at this point.
+                self.data.rotate_right(n as usize);
+                self.mask <<= n;
+            }
+
+            /// Create a by-reference iterator for this block.
+            pub fn iter(&self) -> iter::$iter<'_, T> {
+                iter::$iter { data: &self.data, mask: self.mask }
+            }
+
+            /// Create a by-reference iterator over the occupied entries along with their (actual)
+            /// slot indices. Collecting this iterator (after cloning the values) round-trips the block.
+            pub fn iter_entries(&self) -> iter::$entries<'_, T> {
+                iter::$entries { data: &self.data, mask: self.mask }
+            }
+
+            /// Create a mutable by-reference iterator over the occupied entries along with their
+            /// (actual) slot indices.
+            pub fn iter_entries_mut(&mut self) -> iter::$entries_mut<'_, T> {
+                iter::$entries_mut { slots: self.data.iter_mut().enumerate(), mask: self.mask }
+            }
+
+            /// Consume the block into a by-value iterator over the occupied entries along with their
+            /// (actual) slot indices. Collecting this iterator moves the entries losslessly.
+            pub fn into_entries(self) -> iter::$into_entries<T> {
+                iter::$into_entries { inner: self.into_iter() }
+            }
+
+            /// Returns a snapshot of the occupancy mask which renders as a fixed-width bit string
+            /// (index `0` on the left) or as the raw hexadecimal mask. See [`Occupancy`](occupancy::Occupancy).
+            pub const fn occupancy(&self) -> occupancy::Occupancy {
+                occupancy::Occupancy::new(self.mask as u128, Self::CAPACITY)
+            }
+
+            /// Returns a copy of the occupancy mask, where bit `i` is set if and only if index `i`
+            /// is occupied. This is handy for interop with bit-manipulation code.
+            pub const fn occupied_mask(&self) -> $int {
+                self.mask
+            }
+
+            /// Create an iterator over the occupied indices in ascending order. Only the set bits
+            /// of the mask are visited (without touching the data), so this runs in time
+            /// proportional to the number of occupied slots.
+            pub const fn occupied_indices(&self) -> iter::$indices {
+                iter::$indices { mask: self.mask }
+            }
+
+            /// Create a draining iterator which removes every entry (along with its index) from
+            /// the block. The block is empty once the iterator is dropped, even if it was not
+            /// fully consumed.
+            pub fn drain(&mut self) -> iter::$drain<'_, T> {
+                iter::$drain { block: self }
+            }
+
+            /// Create an iterator which removes and yields (along with their indices) only the
+            /// entries for which `pred` returns `true`. Non-matching entries are left untouched.
+            /// If the iterator is dropped early, the unvisited entries remain in the block.
+            pub fn extract_if<F>(&mut self, pred: F) -> iter::$extract_if<'_, T, F>
+            where
+                F: FnMut(usize, &mut T) -> bool,
+            {
+                iter::$extract_if { block: self, index: 0..Self::CAPACITY as usize, pred }
+            }
+
+            /// Create an iterator over every slot of this block (including vacant ones). Each slot
+            /// is yielded as a proxy that may inspect, modify, take, or insert its value.
+            pub fn slots_mut(&mut self) -> iter::$slots_mut<'_, T> {
+                iter::$slots_mut {
+                    slots: self.data.iter_mut().enumerate(),
+                    mask: core::cell::Cell::from_mut(&mut self.mask),
+                }
+            }
+        }
+
+        impl<T> FixedBlock for $name<T> {
+            type Item = T;
+            type Rebind<U> = $name<U>;
+            const CAPACITY: u32 = <$int>::BITS;
+
+            fn is_vacant(&self, index: usize) -> bool {
+                self.is_vacant(index)
+            }
+
+            fn len(&self) -> u32 {
+                self.len()
+            }
+
+            fn get(&self, index: usize) -> Option<&T> {
+                self.get(index)
+            }
+
+            fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+                self.get_mut(index)
+            }
+
+            fn insert(&mut self, index: usize, val: T) -> Option<T> {
+                self.insert(index, val)
+            }
+
+            fn remove(&mut self, index: usize) -> Option<T> {
+                self.remove(index)
+            }
+        }
+
+        impl<T: Clone> $name<T> {
+            /// For every bit set in `mask`, makes the corresponding slot of `self` mirror the slot
+            /// of `source`: the value is cloned if `source` occupies the slot, otherwise the slot of
+            /// `self` is vacated. Slots outside the `mask` are left untouched. Replaced values are
+            /// dropped.
+            pub fn clone_from_mask(&mut self, source: &Self, mask: $int) {
+                let mut pending = mask;
+                while pending != 0 {
+                    let idx = pending.trailing_zeros() as usize;
+                    pending &= pending - 1;
+                    drop(self.set(idx, source.get(idx).cloned()));
+                }
+            }
+
+            /// Creates a full block where every slot holds a clone of `val`. The original `val` is
+            /// moved into the last slot, which saves one clone. See [`from_elem`](Self::from_elem)
+            /// for the `const` version.
+            pub fn splat(val: T) -> Self {
+                let mut block = Self::default();
+                block.fill(val);
+                block
+            }
+
+            /// Occupies every vacant slot with a clone of `val`, leaving the occupied slots untouched.
+            /// The original `val` is moved into the last vacancy (or dropped if the block is full).
+            pub fn fill_vacancies(&mut self, val: T) {
+                let Some(last) = self.highest_vacant_index_in(<$int>::MAX) else {
+                    return;
+                };
+
+                let mut vacant = !self.mask & !(1 << last);
+                while vacant != 0 {
+                    let idx = vacant.trailing_zeros() as usize;
+                    vacant &= vacant - 1;
+                    self.data[idx].write(val.clone());
+                    self.mask |= 1 << idx;
+                }
+
+                self.data[last].write(val);
+                self.mask |= 1 << last;
+            }
+
+            /// Drops every occupied value and then occupies every slot with a clone of `val`. The
+            /// original `val` is moved into the last slot, which saves one clone.
+            pub fn fill(&mut self, val: T) {
+                self.clear();
+                let last = Self::CAPACITY as usize - 1;
+                for idx in 0..last {
+                    self.data[idx].write(val.clone());
+                    self.mask |= 1 << idx;
+                }
+                self.data[last].write(val);
+                self.mask |= 1 << last;
+            }
+        }
+
+        impl<T: Copy> $name<T> {
+            /// Creates a full block where every slot holds a copy of `val` in a `const` context.
+            ///
+            /// ```rust
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<u8> = option_block::", stringify!($name), "::from_elem(7);")]
+            /// assert!(TABLE.is_full());
+            /// assert!(TABLE.iter().all(|&val| val == 7));
+            /// ```
+            pub const fn from_elem(val: T) -> Self {
+                Self { data: [MaybeUninit::new(val); <$int>::BITS as usize], mask: <$int>::MAX }
+            }
+
+            /// Creates a block from a list of `(index, value)` entries in a `const` context,
+            /// which makes it possible to define sparse `static` tables.
+            ///
+            /// ```rust
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<u8> = option_block::", stringify!($name), "::from_entries([(3, 7), (5, 1)]);")]
+            /// assert_eq!(TABLE.get(3), Some(&7));
+            /// assert_eq!(TABLE.get(5), Some(&1));
+            /// assert_eq!(TABLE.len(), 2);
+            /// ```
+            ///
+            /// # Panic
+            /// Panics if any index is out of bounds or if any index appears more than once.
+            /// In a `const` context, these are compile-time errors.
+            ///
+            /// ```compile_fail
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<u8> = option_block::", stringify!($name), "::from_entries([(1, 0), (1, 1)]);")]
+            /// ```
+            ///
+            /// ```compile_fail
+            #[doc = concat!("static TABLE: option_block::", stringify!($name), "<u8> = option_block::", stringify!($name), "::from_entries([(", stringify!($int), "::BITS as usize, 0)]);")]
+            /// ```
+            pub const fn from_entries<const K: usize>(entries: [(usize, T); K]) -> Self {
+                Self::from_pairs(entries)
+            }
+        }
+
+        impl<T: PartialEq> $name<T> {
+            /// Lazily computes the [changes](diff::Change) that transform `self` into `other`,
+            /// in ascending index order. Unchanged slots are skipped.
+            pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = diff::Change<'a, T>> + 'a {
+                use diff::Change;
+                (0..Self::CAPACITY as usize).filter_map(move |idx| match (self.get(idx), other.get(idx)) {
+                    (None, Some(new)) => Some(Change::Added(idx, new)),
+                    (Some(_), None) => Some(Change::Removed(idx)),
+                    (Some(old), Some(new)) if old != new => Some(Change::Modified(idx, new)),
+                    _ => None,
+                })
+            }
+        }
+
+        impl<T: Default> $name<T> {
+            /// Creates a block whose slots `0..len` are occupied by the default value.
+            ///
+            /// # Panic
+            /// Panics if `len > CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+            pub fn with_len(len: usize) -> Self {
+                let mut block = Self::default();
+                block.resize_with(len, Default::default);
+                block
+            }
+
+            /// Convenience wrapper for the [`get_or_else`](Self::get_or_else) method.
+            pub fn get_or_default(&mut self, index: usize) -> &mut T {
+                self.get_or_else(index, Default::default)
+            }
+        }
+    };
+}
+
+impl_blocked_optional! {
+    /// A fixed block of optionals masked by a [`u8`](u8),
+    /// which may thus contain at most 8 elements.
+    Block8 Block8IntoIter Block8Iter Block8SlotsMut Block8Drain
+    Block8ExtractIf Block8Indices Block8Entries Block8EntriesMut
+    Block8IntoEntries u8
+}
+
+impl_blocked_optional! {
+    /// A fixed block of optionals masked by a [`u16`](u16),
+    /// which may thus contain at most 16 elements.
+    Block16 Block16IntoIter Block16Iter Block16SlotsMut Block16Drain
+    Block16ExtractIf Block16Indices Block16Entries Block16EntriesMut
+    Block16IntoEntries u16
+}
+
+impl_blocked_optional! {
+    /// A fixed block of optionals masked by a [`u32`](u32),
+    /// which may thus contain at most 32 elements.
+    Block32 Block32IntoIter Block32Iter Block32SlotsMut Block32Drain
+    Block32ExtractIf Block32Indices Block32Entries Block32EntriesMut
+    Block32IntoEntries u32
+}
+
+impl_blocked_optional! {
+    /// A fixed block of optionals masked by a [`u64`](u64),
+    /// which may thus contain at most 64 elements.
+    Block64 Block64IntoIter Block64Iter Block64SlotsMut Block64Drain
+    Block64ExtractIf Block64Indices Block64Entries Block64EntriesMut
+    Block64IntoEntries u64
+}
+
+impl_blocked_optional! {
+    /// A fixed block of optionals masked by a [`u128`](u128),
+    /// which may thus contain at most 128 elements.
+    Block128 Block128IntoIter Block128Iter Block128SlotsMut Block128Drain
+    Block128ExtractIf Block128Indices Block128Entries Block128EntriesMut
+    Block128IntoEntries u128
+}
+
+macro_rules! impl_interleave {
+    ($($wide:ident $narrow:ident)*) => {$(
+        impl<T> $wide<T> {
+            #[doc = concat!("Multiplexes two [`", stringify!($narrow), "`](", stringify!($narrow), ") blocks into one by placing")]
+            /// the entry `i` of `even` at index `2 * i` and the entry `i` of `odd` at index `2 * i + 1`.
+            /// Values are moved, never cloned.
+            pub fn interleave(mut even: $narrow<T>, mut odd: $narrow<T>) -> Self {
+                let mut block = Self::default();
+                for idx in 0..$narrow::<T>::CAPACITY as usize {
+                    if let Some(val) = even.remove(idx) {
+                        block.insert(2 * idx, val);
+                    }
+                    if let Some(val) = odd.remove(idx) {
+                        block.insert(2 * idx + 1, val);
+                    }
+                }
+                block
+            }
+
+            /// Demultiplexes the block into its even-indexed and odd-indexed entries. This is the
+            /// inverse of the [`interleave`](Self::interleave) method.
+            pub fn deinterleave(mut self) -> ($narrow<T>, $narrow<T>) {
+                let mut even = $narrow::default();
+                let mut odd = $narrow::default();
+                for idx in 0..$narrow::<T>::CAPACITY as usize {
+                    if let Some(val) = self.remove(2 * idx) {
+                        even.insert(idx, val);
+                    }
+                    if let Some(val) = self.remove(2 * idx + 1) {
+                        odd.insert(idx, val);
+                    }
+                }
+                (even, odd)
+            }
+        }
+    )*};
+}
+
+impl_interleave! {
+    Block16 Block8
+    Block32 Block16
+    Block64 Block32
+    Block128 Block64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capacity_tests() {
+        assert_eq!(Block8::<()>::CAPACITY, 8);
+        assert_eq!(Block16::<()>::CAPACITY, 16);
+        assert_eq!(Block32::<()>::CAPACITY, 32);
+        assert_eq!(Block64::<()>::CAPACITY, 64);
+        assert_eq!(Block128::<()>::CAPACITY, 128);
+    }
+
+    #[test]
+    fn size_tests() {
+        use core::mem::size_of;
+        assert_eq!(size_of::<Block8<u8>>(), 8 + 1);
+        assert_eq!(size_of::<Block16<u8>>(), 16 + 2);
+        assert_eq!(size_of::<Block32<u8>>(), 32 + 4);
+        assert_eq!(size_of::<Block64<u8>>(), 64 + 8);
+        assert_eq!(size_of::<Block128<u8>>(), 128 + 16);
+    }
+
+    #[test]
+    fn insert_replace_semantics() {
+        let mut block = Block8::default();
+        assert!(block.is_empty());
+
+        assert!(block.insert(0, 32).is_none());
+        assert!(block.insert(1, 64).is_none());
+
+        assert_eq!(block.insert(0, 1), Some(32));
+        assert_eq!(block.insert(1, 2), Some(64));
+
+        assert_eq!(block.remove(0), Some(1));
+        assert_eq!(block.remove(1), Some(2));
+
+        assert!(block.is_empty());
+    }
+
+    #[test]
+    #[cfg(any(debug_assertions, not(feature = "unchecked-index")))]
+    #[should_panic]
+    fn out_of_range_index() {
+        let block = Block8::<u8>::default();
+        let _ = block.get(8);
+    }
+
+    #[test]
+    fn check_iterators() {
+        let block = Block8::<usize>::from([0, 1, 2, 3, 4, 5, 6, 7]);
+
+        for (idx, &val) in block.iter().enumerate() {
+            assert_eq!(idx, val);
+        }
+
+        for (idx, val) in block.into_iter().enumerate() {
+            assert_eq!(idx, val);
+        }
+    }
+
+    #[test]
+    fn indexing_operations() {
+        use core::ops::Range;
+        type Block = Block8<usize>;
+        const RANGE: Range<usize> = 0..Block::CAPACITY as usize;
+        let mut block = Block::from([0, 1, 2, 3, 4, 5, 6, 7]);
+
+        for i in RANGE {
+            assert_eq!(block[i], i);
+        }
+
+        for i in RANGE {
+            block[i] *= 2;
+        }
+
+        for i in RANGE {
+            assert_eq!(block[i], i * 2);
+        }
+    }
+
+    #[test]
+    fn value_conversions() {
+        let block: Block8<u8> = [(1, 10), (4, 200)].into_iter().collect();
+        let block = block.convert::<u32>();
+        assert_eq!(block.len(), 2);
+        assert_eq!(block.get(1), Some(&10));
+        assert_eq!(block.get(4), Some(&200));
+
+        let result = block.clone().try_convert::<u8>();
+        assert_eq!(result.map(|block| block.len()), Ok(2));
+
+        let block: Block16<i32> = [(2, 1), (7, -1), (9, 3)].into_iter().collect();
+        let (idx, _) = block.try_convert::<u32>().unwrap_err();
+        assert_eq!(idx, 7);
+    }
+
+    #[test]
+    fn slot_proxies() {
+        let mut block = Block8::<usize>::from([0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(block.remove(2), Some(2));
+        assert_eq!(block.remove(4), Some(4));
+
+        for mut slot in block.slots_mut() {
+            let idx = slot.index();
+            if idx % 2 == 1 {
+                assert_eq!(slot.take(), Some(idx));
+                assert!(slot.is_vacant());
+            } else if slot.is_vacant() {
+                assert!(slot.get().is_none());
+                assert!(slot.insert(idx * 10).is_none());
+                assert!(!slot.is_vacant());
+            } else if let Some(val) = slot.get_mut() {
+                *val += 100;
+            }
+        }
+
+        assert_eq!(block.len(), 4);
+        assert_eq!(block.get(0), Some(&100));
+        assert_eq!(block.get(2), Some(&20));
+        assert_eq!(block.get(4), Some(&40));
+        assert_eq!(block.get(6), Some(&106));
+        for idx in [1, 3, 5, 7] {
+            assert!(block.is_vacant(idx));
+        }
+    }
+
+    #[test]
+    fn split_one_mut() {
+        let mut block: Block16<u32> = [(0, 1), (3, 10), (7, 100), (15, 1000)].into_iter().collect();
+        assert!(block.split_one_mut(1).is_none());
+
+        let (target, others) = block.split_one_mut(7).unwrap();
+        *target += others.map(|(idx, &val)| idx as u32 * val).sum::<u32>();
+        assert_eq!(block.get(7), Some(&(100 + 30 + 15000)));
+
+        let (target, others) = block.split_one_mut(0).unwrap();
+        assert!(others.map(|(idx, _)| idx).eq([3, 7, 15]));
+        *target = 0;
+        assert_eq!(block.get(0), Some(&0));
+
+        let (_, others) = block.split_one_mut(15).unwrap();
+        assert!(others.map(|(idx, _)| idx).eq([0, 3, 7]));
+    }
+
+    #[test]
+    fn dense_resizing() {
+        let mut block = Block8::<u8>::with_len(3);
+        assert_eq!(block.len(), 3);
+        assert!(block.iter().eq(&[0, 0, 0]));
 
-        for i in RANGE {
-            assert_eq!(block[i], i * 2);
+        let mut counter = 0;
+        block.resize_with(6, || {
+            counter += 1;
+            counter
+        });
+        assert!(block.iter().eq(&[0, 0, 0, 1, 2, 3]));
+
+        block.resize_with(6, || unreachable!());
+        assert_eq!(block.len(), 6);
+
+        block.resize_with(2, || unreachable!());
+        assert!(block.iter().eq(&[0, 0]));
+        assert!(block.is_vacant(2));
+
+        block.resize_with(8, Default::default);
+        assert!(block.is_full());
+        assert_eq!(Block128::<()>::with_len(128).len(), 128);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sparse_resizing() {
+        let mut block = Block8::<u8>::default();
+        block.insert(1, 0);
+        block.resize_with(4, Default::default);
+    }
+
+    #[test]
+    fn index_of_ref() {
+        let block: Block32<u16> = [(0, 0), (5, 50), (31, 310)].into_iter().collect();
+        for idx in [0, 5, 31] {
+            assert_eq!(block.index_of_ref(block.get(idx).unwrap()), Some(idx));
+        }
+
+        let indices: [_; 3] = core::array::from_fn(|i| block.iter().nth(i).and_then(|val| block.index_of_ref(val)));
+        assert_eq!(indices, [Some(0), Some(5), Some(31)]);
+
+        let other = block.clone();
+        assert_eq!(other.index_of_ref(block.get(5).unwrap()), None);
+        assert_eq!(block.index_of_ref(&50), None);
+
+        let zst = Block8::from([(); 8]);
+        assert_eq!(zst.index_of_ref(zst.get(0).unwrap()), None);
+    }
+
+    #[test]
+    fn scored_vacancies() {
+        let mut block = Block8::<char>::default();
+        assert_eq!(block.insert_at_vacancy_by(|idx| idx as i32, 'a'), Ok(7));
+        assert_eq!(block.insert_at_vacancy_by(|idx| idx as i32, 'b'), Ok(6));
+        assert_eq!(block.insert_at_vacancy_by(|_| 0, 'c'), Ok(0));
+        assert_eq!(block.insert_at_vacancy_by(|idx| -((idx as i32 - 3).abs()), 'd'), Ok(3));
+
+        let mut calls = 0;
+        let vacancies = Block8::<char>::CAPACITY - block.len();
+        let _ = block.insert_at_vacancy_by(
+            |_| {
+                calls += 1;
+                0
+            },
+            'e',
+        );
+        assert_eq!(calls, vacancies);
+
+        for val in ['f', 'g', 'h'] {
+            assert!(block.insert_at_vacancy_by(|_| 0, val).is_ok());
+        }
+        assert!(block.is_full());
+        assert_eq!(block.insert_at_vacancy_by(|_| unreachable!(), 'z'), Err('z'));
+    }
+
+    #[test]
+    fn const_sparse_tables() {
+        static TABLE: Block16<u8> = Block16::from_entries([(3, 7), (9, 1)]);
+        const EMPTY: Block128<u8> = Block128::from_entries([]);
+
+        let mut block = Block16::default();
+        block.insert(3, 7);
+        block.insert(9, 1);
+
+        assert_eq!(TABLE.len(), block.len());
+        for idx in 0..Block16::<u8>::CAPACITY as usize {
+            assert_eq!(TABLE.get(idx), block.get(idx));
+        }
+
+        assert!(EMPTY.is_empty());
+    }
+
+    #[test]
+    fn mutable_joins() {
+        let mut positions: Block8<i32> = [(0, 0), (1, 10), (3, 30), (5, 50)].into_iter().collect();
+        let velocities: Block8<i32> = [(1, 1), (2, 2), (5, 5)].into_iter().collect();
+
+        for (idx, pos, vel) in positions.zip_mut(&velocities) {
+            assert!(idx == 1 || idx == 5);
+            *pos += vel;
+        }
+
+        assert_eq!(positions.get(0), Some(&0));
+        assert_eq!(positions.get(1), Some(&11));
+        assert_eq!(positions.get(3), Some(&30));
+        assert_eq!(positions.get(5), Some(&55));
+
+        let mut counters: Block8<u8> = [(2, 0), (3, 0), (5, 0)].into_iter().collect();
+        for (_, pos, count) in positions.zip_mut_mut(&mut counters) {
+            *pos = 0;
+            *count += 1;
+        }
+
+        assert!(positions.iter().eq(&[0, 11, 0, 0]));
+        assert!(counters.iter().eq(&[0, 1, 1]));
+    }
+
+    #[test]
+    fn state_machine_in_place() {
+        #[derive(Debug, PartialEq)]
+        enum State {
+            Idle,
+            Running(u8),
+            Done,
+        }
+
+        impl State {
+            fn step(self) -> Self {
+                match self {
+                    Self::Idle => Self::Running(0),
+                    Self::Running(n) if n >= 1 => Self::Done,
+                    Self::Running(n) => Self::Running(n + 1),
+                    Self::Done => Self::Done,
+                }
+            }
+        }
+
+        let mut block: Block8<_> = [(1, State::Idle), (4, State::Running(1))].into_iter().collect();
+        block.map_in_place(|_, state| state.step());
+        assert_eq!(block.get(1), Some(&State::Running(0)));
+        assert_eq!(block.get(4), Some(&State::Done));
+        assert_eq!(block.len(), 2);
+
+        block.map_in_place(|idx, state| if idx == 1 { state.step().step() } else { state });
+        assert!(block.iter().eq(&[State::Done, State::Done]));
+    }
+
+    #[test]
+    fn interleaving_round_trip() {
+        let mut seed = 0x1234_5678_u32;
+        for _ in 0..64 {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+
+            let even: Block32<u32> = (0..32).filter(|i| seed & (1 << i) != 0).map(|i| (i, i as u32)).collect();
+            let odd: Block32<u32> = (0..32).filter(|i| seed & (1 << i) == 0).map(|i| (i, i as u32 + 100)).collect();
+            let (even_mask, odd_mask) = (even.mask, odd.mask);
+
+            let wide = Block64::interleave(even, odd);
+            for i in 0..32 {
+                assert_eq!(wide.mask & (1 << (2 * i)) != 0, even_mask & (1 << i) != 0);
+                assert_eq!(wide.mask & (1 << (2 * i + 1)) != 0, odd_mask & (1 << i) != 0);
+            }
+
+            let (even, odd) = wide.deinterleave();
+            assert_eq!(even.mask, even_mask);
+            assert_eq!(odd.mask, odd_mask);
+            for i in 0..32 {
+                assert!(even.get(i).is_none_or(|&val| val == i as u32));
+                assert!(odd.get(i).is_none_or(|&val| val == i as u32 + 100));
+            }
+        }
+
+        let wide = Block16::interleave(Block8::from([0; 8]), Block8::default());
+        assert_eq!(wide.mask, 0x5555);
+        let wide = Block128::interleave(Block64::default(), Block64::from([0; 64]));
+        assert_eq!(wide.mask, (u128::MAX / 3) << 1);
+    }
+
+    #[test]
+    fn selective_extraction() {
+        let mut block = Block8::from([0, 1, 2, 3, 4, 5, 6, 7]);
+        let mut visited = 0;
+        {
+            let mut expired = block.extract_if(|_, val| {
+                visited += 1;
+                *val % 3 == 0
+            });
+            assert_eq!(expired.next(), Some((0, 0)));
+            assert_eq!(expired.next(), Some((3, 3)));
+        }
+
+        assert_eq!(visited, 4);
+        assert_eq!(block.len(), 6);
+        assert_eq!(block.get(6), Some(&6));
+
+        let odd: Block8<_> = block.extract_if(|_, val| *val % 2 == 1).collect();
+        assert!(odd.iter().eq(&[1, 5, 7]));
+        assert!(block.iter().eq(&[2, 4, 6]));
+    }
+
+    #[test]
+    fn occupied_index_iteration() {
+        macro_rules! check {
+            ($block:ident) => {{
+                let last = $block::<()>::CAPACITY as usize - 1;
+                let block: $block<()> = [(0, ()), (3, ()), (last, ())].into_iter().collect();
+                let indices = block.occupied_indices();
+                assert_eq!(indices.len(), 3);
+                assert!(indices.clone().eq([0, 3, last]));
+                assert!(indices.rev().eq([last, 3, 0]));
+
+                let mut indices = block.occupied_indices();
+                assert_eq!(indices.next(), Some(0));
+                assert_eq!(indices.next_back(), Some(last));
+                assert_eq!(indices.next(), Some(3));
+                assert_eq!(indices.next_back(), None);
+                assert!($block::<()>::default().occupied_indices().next().is_none());
+            }};
+        }
+
+        check!(Block8);
+        check!(Block16);
+        check!(Block32);
+        check!(Block64);
+        check!(Block128);
+    }
+
+    #[test]
+    fn indexed_iteration() {
+        let mut block: Block16<u32> = [(2, 20), (9, 90), (15, 150)].into_iter().collect();
+        assert!(block.iter_entries().eq([(2, &20), (9, &90), (15, &150)]));
+
+        for (idx, val) in block.iter_entries_mut() {
+            *val += idx as u32;
+        }
+        assert!(block.iter_entries().eq([(2, &22), (9, &99), (15, &165)]));
+
+        let copy: Block16<u32> = block.iter_entries().map(|(idx, &val)| (idx, val)).collect();
+        assert!(copy.iter_entries().eq(block.iter_entries()));
+        assert!(Block16::<u32>::default().iter_entries_mut().next().is_none());
+    }
+
+    #[test]
+    fn consuming_indexed_iteration() {
+        let block: Block16<u32> = [(2, 20), (9, 90), (15, 150)].into_iter().collect();
+        let moved: Block64<u32> = block.into_entries().collect();
+        assert!(moved.iter_entries().eq([(2, &20), (9, &90), (15, &150)]));
+
+        let mut entries = moved.into_entries();
+        assert_eq!(entries.next(), Some((2, 20)));
+        assert!(entries.eq([(9, 90), (15, 150)]));
+    }
+
+    #[test]
+    fn reversed_into_iter() {
+        let block: Block32<u32> = [(0, 1), (12, 2), (31, 3)].into_iter().collect();
+        assert!(block.clone().into_iter().rev().eq([3, 2, 1]));
+
+        let mut iter = block.into_iter();
+        assert_eq!(iter.next_back(), Some(3));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(2));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn exact_size_hints() {
+        let mut block: Block128<u32> = [(0, 0), (63, 1), (64, 2), (127, 3)].into_iter().collect();
+
+        let mut iter = block.iter();
+        for remaining in (0..=4).rev() {
+            assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+            assert_eq!(iter.len(), remaining);
+            iter.next();
+        }
+        assert_eq!(iter.len(), 0);
+
+        let mut entries = block.iter_entries_mut();
+        assert_eq!(entries.len(), 4);
+        entries.next();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(block.iter_entries().len(), 4);
+
+        let mut iter = block.clone().into_iter();
+        assert_eq!(iter.len(), 4);
+        iter.next_back();
+        iter.next();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(block.clone().into_entries().len(), 4);
+
+        let mut drain = block.drain();
+        assert_eq!(drain.len(), 4);
+        drain.next();
+        assert_eq!(drain.len(), 3);
+        drop(drain);
+
+        let sparse: Block8<u8> = [(7, 1)].into_iter().collect();
+        let mut iter = sparse.iter();
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn fused_iterators() {
+        macro_rules! check_fused {
+            ($($block:ident)*) => {$(
+                let sparse: $block<u32> = [(0, 0), ($block::<u32>::CAPACITY as usize - 1, 1)].into_iter().collect();
+                let full: $block<u32> = (0..$block::<u32>::CAPACITY).enumerate().collect();
+                for block in [sparse, full] {
+                    let mut iter = block.iter();
+                    iter.by_ref().for_each(drop);
+                    let mut entries = block.iter_entries();
+                    entries.by_ref().for_each(drop);
+                    let mut indices = block.occupied_indices();
+                    indices.by_ref().for_each(drop);
+                    let mut into_iter = block.clone().into_iter();
+                    into_iter.by_ref().for_each(drop);
+                    for _ in 0..4 {
+                        assert!(iter.next().is_none());
+                        assert!(entries.next().is_none());
+                        assert!(indices.next().is_none());
+                        assert!(into_iter.next().is_none());
+                        assert!(into_iter.next_back().is_none());
+                    }
+                }
+            )*};
+        }
+
+        check_fused!(Block8 Block16 Block32 Block64 Block128);
+    }
+
+    #[test]
+    fn cloning_borrowed_iterators() {
+        struct Opaque(#[allow(dead_code)] u8);
+        let block: Block8<Opaque> = [(1, Opaque(1)), (6, Opaque(6))].into_iter().collect();
+
+        let mut iter = block.iter();
+        iter.next();
+        let copy = iter.clone();
+        assert_eq!(copy.len(), 1);
+        assert!(core::ptr::eq(&block[6], copy.clone().next().unwrap()));
+        assert!(core::ptr::eq(iter.next().unwrap(), copy.last().unwrap()));
+    }
+
+    #[test]
+    fn equality() {
+        let left: Block32<u32> = [(0, 1), (9, 2)].into_iter().collect();
+        let mut right = Block32::default();
+        assert_ne!(left, right);
+
+        assert!(right.insert(9, 2).is_none());
+        assert!(right.insert(0, 1).is_none());
+        assert_eq!(left, right);
+        let (shared, exclusive) = (&left, &mut right);
+        assert!(shared == exclusive);
+
+        // Same values, but at different indices.
+        let shifted: Block32<u32> = [(1, 1), (9, 2)].into_iter().collect();
+        assert_ne!(left, shifted);
+
+        assert_eq!(right.insert(9, 3), Some(2));
+        assert_ne!(left, right);
+
+        // Stale data left behind in a vacated slot must not matter.
+        assert_eq!(right.remove(9), Some(3));
+        let mut other = left.clone();
+        assert_eq!(other.remove(9), Some(2));
+        assert_eq!(right, other);
+    }
+
+    #[test]
+    fn lexicographic_ordering() {
+        fn as_array<const N: usize>(block: impl Fn(usize) -> Option<u8>) -> [Option<u8>; N] {
+            core::array::from_fn(block)
+        }
+
+        let cases: [&[(usize, u8)]; 8] = [
+            &[],
+            &[(0, 0)],
+            &[(0, 1)],
+            &[(1, 0)],
+            &[(0, 0), (7, 0)],
+            &[(0, 0), (6, 9)],
+            &[(3, 3), (4, 4)],
+            &[(0, 5), (1, 5), (2, 5), (3, 5), (4, 5), (5, 5), (6, 5), (7, 5)],
+        ];
+
+        for left in cases {
+            for right in cases {
+                let (a, b): (Block8<u8>, Block8<u8>) =
+                    (left.iter().copied().collect(), right.iter().copied().collect());
+                let expected = as_array::<8>(|i| a.get(i).copied()).cmp(&as_array::<8>(|i| b.get(i).copied()));
+                assert_eq!(a.cmp(&b), expected);
+                assert_eq!(a.partial_cmp(&b), Some(expected));
+
+                let shift = |&(idx, val): &(usize, u8)| (idx * 2 + 1, val);
+                let a: Block16<u8> = left.iter().map(shift).collect();
+                let b: Block16<u8> = right.iter().map(shift).collect();
+                let expected = as_array::<16>(|i| a.get(i).copied()).cmp(&as_array::<16>(|i| b.get(i).copied()));
+                assert_eq!(a.cmp(&b), expected);
+            }
         }
+
+        let nan: Block8<f32> = [(2, f32::NAN)].into_iter().collect();
+        assert!(nan.partial_cmp(&nan).is_none());
     }
 
     #[test]
@@ -389,4 +2589,480 @@ mod tests {
         assert_eq!(block.get_or(1, 100), &mut 10);
         assert_eq!(block.get_or_default(2), &mut 0);
     }
+
+    #[test]
+    fn vacancies_at_boundaries() {
+        let mut small = Block8::<u8>::default();
+        assert_eq!(small.vacancies(), 8);
+        for idx in 0..7 {
+            small.insert(idx, 0);
+        }
+        assert_eq!(small.vacancies(), 1);
+        assert_eq!(small.insert_at_vacancy_by(|_| 0, 0), Ok(7));
+        assert_eq!(small.vacancies(), 0);
+        assert_eq!(small.insert_at_vacancy_by(|_| 0, 1), Err(1));
+
+        let mut large = Block128::<u8>::default();
+        assert_eq!(large.vacancies(), 128);
+        for idx in 1..128 {
+            large.insert(idx, 0);
+        }
+        assert_eq!(large.vacancies(), 1);
+        assert_eq!(large.insert_at_vacancy_by(|_| 0, 0), Ok(0));
+        assert_eq!(large.vacancies(), 0);
+    }
+
+    #[test]
+    fn repr_c_layout() {
+        use core::mem::{align_of, offset_of, size_of};
+
+        macro_rules! check_layout {
+            ($block:ident $int:ty: $($payload:ty),*) => {$(
+                let capacity = $block::<$payload>::CAPACITY as usize;
+                let data = capacity * size_of::<$payload>();
+                let align = align_of::<$payload>().max(align_of::<$int>());
+                assert_eq!(offset_of!($block<$payload>, data), 0);
+                assert_eq!(offset_of!($block<$payload>, mask), data.next_multiple_of(align_of::<$int>()));
+                assert_eq!(size_of::<$block<$payload>>(), (offset_of!($block<$payload>, mask) + size_of::<$int>()).next_multiple_of(align));
+                assert_eq!(align_of::<$block<$payload>>(), align);
+
+                let mut block = $block::<$payload>::default();
+                let base = core::ptr::addr_of!(block).cast::<u8>();
+                assert_eq!(block.as_ptr().cast::<u8>(), base);
+                assert_eq!(block.mask_ptr().cast::<u8>(), base.wrapping_add(offset_of!($block<$payload>, mask)));
+                assert_eq!(block.as_mut_ptr().cast_const(), block.as_ptr());
+            )*};
+        }
+
+        check_layout!(Block8 u8: u8, u16, u64, [u8; 3], (u8, u32));
+        check_layout!(Block16 u16: u8, u16, u64, [u8; 3], (u8, u32));
+        check_layout!(Block32 u32: u8, u16, u64, [u8; 3], (u8, u32));
+        check_layout!(Block64 u64: u8, u16, u64, [u8; 3], (u8, u32));
+        check_layout!(Block128 u128: u8, u16, u64, [u8; 3], (u8, u32));
+    }
+
+    #[test]
+    fn raw_pointer_access() {
+        let mut block: Block16<u32> = [(3, 30)].into_iter().collect();
+        // SAFETY: Slot `3` is occupied and slot `5` is within bounds.
+        unsafe {
+            assert_eq!(*block.as_ptr().add(3), 30);
+            assert_eq!(*block.mask_ptr(), 1 << 3);
+            block.as_mut_ptr().add(5).write(50);
+        }
+        assert!(block.is_vacant(5));
+    }
+
+    #[test]
+    fn batched_lookups() {
+        let block: Block32<u8> = [(1, 10), (20, 200)].into_iter().collect();
+        assert_eq!(block.get_many([20, 0, 1, 20]), [Some(&200), None, Some(&10), Some(&200)]);
+        assert_eq!(block.get_many([]), [None::<&u8>; 0]);
+
+        let empty = Block32::<u8>::default();
+        assert_eq!(empty.get_many([0, 15, 15, 31]), [None; 4]);
+    }
+
+    #[test]
+    #[cfg(any(debug_assertions, not(feature = "unchecked-index")))]
+    #[should_panic]
+    fn batched_lookup_out_of_bounds() {
+        Block8::<u8>::default().get_many([0, 8]);
+    }
+
+    #[test]
+    fn try_insert_refuses_occupied() {
+        let mut block = Block8::<u8>::default();
+        let val = block.try_insert(4, 1).unwrap();
+        *val += 1;
+        assert_eq!(block.get(4), Some(&2));
+
+        let err = block.try_insert(4, 9).unwrap_err();
+        assert_eq!(err.index, 4);
+        assert_eq!(err.value, 9);
+        *err.existing += 1;
+        assert_eq!(block.get(4), Some(&3));
+        assert_eq!(block.len(), 1);
+    }
+
+    #[test]
+    fn checked_accessors() {
+        let mut block: Block16<u8> = [(0, 1), (9, 2)].into_iter().collect();
+        let mut checked = block.clone();
+        for index in 0..16 {
+            assert_eq!(checked.checked_get(index), block.get(index));
+            assert_eq!(checked.checked_get_mut(index), block.get_mut(index));
+            assert_eq!(checked.checked_insert(index, index as u8), Ok(block.insert(index, index as u8)));
+            assert_eq!(checked.occupied_mask(), block.occupied_mask());
+            if index % 3 == 0 {
+                assert_eq!(checked.checked_remove(index), Ok(block.remove(index)));
+            }
+        }
+        assert_eq!(checked, block);
+
+        let error = IndexOutOfRange { index: 16, capacity: 16 };
+        assert!(checked.checked_get(16).is_none());
+        assert!(checked.checked_get_mut(usize::MAX).is_none());
+        assert_eq!(checked.checked_insert(16, 0), Err((error, 0)));
+        assert_eq!(checked.checked_remove(16), Err(error));
+        assert_eq!(checked.occupied_mask(), block.occupied_mask());
+    }
+
+    #[test]
+    fn occupied_updates() {
+        macro_rules! check_updates {
+            ($($block:ident)*) => {$(
+                let last = $block::<u32>::CAPACITY as usize - 1;
+                let mut block: $block<u32> = [(0, 1), (last, 2)].into_iter().collect();
+                assert!(block.update(0, |val| *val += 10));
+                assert!(block.update(last, |val| *val *= 3));
+                assert!(!block.update(1, |_| unreachable!()));
+                assert_eq!(block.map_occupied(last, |val| core::mem::replace(val, 0)), Some(6));
+                assert_eq!(block.map_occupied(last - 1, |_| unreachable!()), None::<()>);
+                assert_eq!(block.get(0), Some(&11));
+                assert_eq!(block.get(last), Some(&0));
+                assert_eq!(block.len(), 2);
+            )*};
+        }
+
+        check_updates!(Block8 Block16 Block32 Block64 Block128);
+    }
+
+    #[test]
+    #[cfg(any(debug_assertions, not(feature = "unchecked-index")))]
+    #[should_panic]
+    fn out_of_range_update() {
+        Block8::<u8>::default().update(8, |_| ());
+    }
+
+    #[test]
+    fn push_into_lowest_vacancy() {
+        let mut pool = Block8::<u8>::default();
+        for expected in 0..4 {
+            assert_eq!(pool.push(expected as u8), Ok(expected));
+        }
+
+        pool.remove(2);
+        pool.remove(0);
+        assert_eq!(pool.push(10), Ok(0));
+        assert_eq!(pool.push(20), Ok(2));
+        assert_eq!(pool.push(40), Ok(4));
+        assert_eq!(pool.get_many([0, 1, 2, 3, 4]), [Some(&10), Some(&1), Some(&20), Some(&3), Some(&40)]);
+
+        while pool.push(0).is_ok() {}
+        assert!(pool.is_full());
+        assert_eq!(pool.push(99), Err(99));
+        pool.remove(6);
+        assert_eq!(pool.push(60), Ok(6));
+    }
+
+    #[test]
+    fn restricted_vacancy_insertion() {
+        let mut block: Block16<u8> = [(1, 0), (2, 0), (9, 0)].into_iter().collect();
+
+        // Fully occupied allowed region
+        assert_eq!(block.lowest_vacant_index_in(0b110), None);
+        assert_eq!(block.highest_vacant_index_in(0b110), None);
+        assert_eq!(block.insert_at_first_vacancy_in(0b110, 7), Err(7));
+        assert_eq!(block.insert_at_last_vacancy_in(0, 7), Err(7));
+
+        // Partially occupied allowed region
+        let allowed = 0b0000_0110_0000_0110;
+        assert_eq!(block.lowest_vacant_index_in(allowed), Some(10));
+        assert_eq!(block.insert_at_first_vacancy_in(allowed, 10), Ok(10));
+        assert_eq!(block.insert_at_first_vacancy_in(allowed, 11), Err(11));
+
+        // Disjoint from the current occupancy
+        let allowed = 0b1000_0000_0011_0000;
+        assert_eq!(block.highest_vacant_index_in(allowed), Some(15));
+        assert_eq!(block.insert_at_last_vacancy_in(allowed, 15), Ok(15));
+        assert_eq!(block.insert_at_last_vacancy_in(allowed, 5), Ok(5));
+        assert_eq!(block.insert_at_first_vacancy_in(allowed, 4), Ok(4));
+        assert!(block.occupied_indices().eq([1, 2, 4, 5, 9, 10, 15]));
+        assert_eq!(block.get_many([4, 5, 10, 15]), [Some(&4), Some(&5), Some(&10), Some(&15)]);
+    }
+
+    #[test]
+    fn random_vacancy_selection() {
+        let mut block: Block128<u8> = (0..128).filter(|idx| idx % 3 != 0).map(|idx| (idx, 0)).collect();
+        assert_eq!(block.vacancies(), 43);
+
+        // A counter visits every vacancy exactly once (and then wraps around).
+        let mut counter = 0..;
+        let mut next = || counter.next().unwrap();
+        for expected in (0..128).step_by(3).chain([0]) {
+            assert_eq!(block.random_vacant_index(&mut next), Some(expected));
+        }
+
+        let mut counter = [5, 0, 40].into_iter();
+        let mut next = || counter.next().unwrap();
+        assert_eq!(block.insert_at_random_vacancy(1, &mut next), Ok(15));
+        assert_eq!(block.insert_at_random_vacancy(2, &mut next), Ok(0));
+        assert_eq!(block.insert_at_random_vacancy(3, &mut next), Ok(126));
+        assert_eq!(block.get_many([0, 15, 126]), [Some(&2), Some(&1), Some(&3)]);
+
+        let mut full = Block8::<u8>::default();
+        while full.insert_at_random_vacancy(0, || 7).is_ok() {}
+        assert_eq!(full.random_vacant_index(|| unreachable!()), None);
+        assert_eq!(full.insert_at_random_vacancy(1, || unreachable!()), Err(1));
+    }
+
+    #[test]
+    fn contiguous_slices() {
+        let mut block: Block32<u8> = [(30, 1), (2, 9), (17, 4), (5, 7)].into_iter().collect();
+        let slice = block.make_contiguous();
+        assert_eq!(slice, [9, 7, 4, 1]);
+
+        slice.sort_unstable();
+        assert_eq!(slice.binary_search(&7), Ok(2));
+        assert_eq!(block.get_many([0, 1, 2, 3]), [Some(&1), Some(&4), Some(&7), Some(&9)]);
+        assert_eq!(block.occupied_mask(), 0b1111);
+
+        assert!(Block32::<u8>::default().make_contiguous().is_empty());
+        let mut full = Block8::<u8>::with_len(8);
+        assert_eq!(full.make_contiguous().len(), 8);
+    }
+
+    #[test]
+    fn contiguous_slice_views() {
+        let mut block = Block16::<u8>::default();
+        assert_eq!(block.as_contiguous_slice(), Some(&[][..]));
+        assert_eq!(block.as_contiguous_slice_mut(), Some(&mut [][..]));
+
+        for val in 0..4 {
+            block.push(val).unwrap();
+        }
+        assert_eq!(block.as_contiguous_slice(), Some(&[0, 1, 2, 3][..]));
+
+        block.remove(1);
+        assert!(block.as_contiguous_slice().is_none());
+        assert!(block.as_contiguous_slice_mut().is_none());
+
+        block.remove(3);
+        block.insert(1, 10);
+        let slice = block.as_contiguous_slice_mut().unwrap();
+        slice.reverse();
+        assert_eq!(block.as_contiguous_slice(), Some(&[2, 10, 0][..]));
+
+        let full = Block8::<u8>::with_len(8);
+        assert_eq!(full.as_contiguous_slice().map(<[_]>::len), Some(8));
+    }
+
+    #[test]
+    fn sparse_lockstep_joins() {
+        let mut positions: Block128<i32> = [(0, 0), (64, 640), (100, 1000), (127, 1270)].into_iter().collect();
+        let mut velocities: Block128<i32> = [(1, 1), (64, 6), (127, 12)].into_iter().collect();
+
+        for (_, pos, vel) in positions.zip_mut_mut(&mut velocities) {
+            *pos += *vel;
+            *vel = 0;
+        }
+
+        assert!(positions.iter_entries().map(|(idx, pos)| (idx, *pos)).eq([
+            (0, 0),
+            (64, 646),
+            (100, 1000),
+            (127, 1282)
+        ]));
+        assert!(velocities.iter_entries().map(|(idx, vel)| (idx, *vel)).eq([(1, 1), (64, 0), (127, 0)]));
+        assert!(positions.zip_mut(&velocities).map(|(idx, ..)| idx).eq([64, 127]));
+        assert!(positions.zip_mut(&Block128::<()>::default()).next().is_none());
+    }
+
+    #[test]
+    fn occupancy_relations() {
+        macro_rules! check_relations {
+            ($($block:ident)*) => {$(
+                let last = $block::<u8>::CAPACITY as usize - 1;
+                let empty = $block::<u8>::default();
+                let small: $block<u8> = [(0, 0), (last, 0)].into_iter().collect();
+                let large: $block<char> = [(0, 'a'), (3, 'b'), (last, 'c')].into_iter().collect();
+                let other: $block<()> = [(3, ()), (4, ())].into_iter().collect();
+
+                // Empty versus empty
+                assert!(empty.is_disjoint(&empty) && empty.is_subset(&empty) && empty.is_superset(&empty));
+
+                // Identical
+                assert!(!small.is_disjoint(&small) && small.is_subset(&small) && small.is_superset(&small));
+                assert!(small.is_subset_mask(small.occupied_mask()) && small.is_superset_mask(small.occupied_mask()));
+
+                // Strict subset
+                assert!(small.is_subset(&large) && !small.is_superset(&large) && large.is_superset(&small));
+                assert!(empty.is_subset(&small) && small.is_superset(&empty) && small.is_disjoint(&empty));
+
+                // Partially overlapping
+                assert!(!large.is_disjoint(&other) && !large.is_subset(&other) && !large.is_superset(&other));
+                assert!(small.is_disjoint(&other) && small.is_disjoint_mask(!small.occupied_mask()));
+                assert!(!large.is_disjoint_mask(1 << 3) && large.is_superset_mask(1 << 3) && !large.is_subset_mask(1 << 3));
+            )*};
+        }
+
+        check_relations!(Block8 Block128);
+
+        const SPARSE: &Block8<u8> = &Block8::from_entries([(1, 0), (6, 0)]);
+        const { assert!(SPARSE.is_disjoint_mask(0b1011_1101) && SPARSE.is_subset(SPARSE)) };
+    }
+
+    #[test]
+    fn set_operators() {
+        let a: Block16<u8> = [(0, 1), (3, 2), (9, 3), (15, 4)].into_iter().collect();
+        let b: Block16<u8> = [(3, 20), (4, 30), (15, 40)].into_iter().collect();
+        let filter: Block16<()> = [(0, ()), (15, ())].into_iter().collect();
+
+        let mut union = a.clone();
+        union.merge(b.clone(), |_, current, _| current);
+        assert_eq!(a.clone() | b.clone(), union);
+        let mut assigned = a.clone();
+        assigned |= b.clone();
+        assert_eq!(assigned, union);
+
+        let mut intersection = a.clone();
+        intersection.intersect_with(&filter);
+        assert_eq!(a.clone() & &filter, intersection);
+        let mut assigned = a.clone();
+        assigned &= &b;
+        assert!(assigned.iter_entries().map(|(idx, val)| (idx, *val)).eq([(3, 2), (15, 4)]));
+
+        let mut difference = a.clone();
+        difference.subtract(&b);
+        assert_eq!(a.clone() - &b, difference);
+        let mut assigned = a;
+        assigned -= &filter;
+        assert!(assigned.iter_entries().map(|(idx, val)| (idx, *val)).eq([(3, 2), (9, 3)]));
+    }
+
+    #[test]
+    fn pairwise_accumulation() {
+        let mut acc: Block32<u32> = [(0, 10), (5, 50), (31, 310)].into_iter().collect();
+        let delta: Block32<u8> = [(5, 5), (6, 6), (31, 1)].into_iter().collect();
+
+        let mut visited = 0u32;
+        acc.apply_pairwise(&delta, |idx, val, d| {
+            visited |= 1 << idx;
+            *val += u32::from(*d);
+        });
+        assert_eq!(visited, 1 << 5 | 1 << 31);
+        assert!(acc.iter_entries().map(|(idx, val)| (idx, *val)).eq([(0, 10), (5, 55), (31, 311)]));
+
+        let disjoint: Block32<u8> = [(1, 1), (2, 2)].into_iter().collect();
+        acc.apply_pairwise(&disjoint, |_, _, _| unreachable!());
+        assert_eq!(acc.len(), 3);
+
+        acc.apply_or_insert_pairwise(&delta, |_, val, d| *val += u32::from(*d), |_, d| u32::from(*d) * 100);
+        assert!(acc.iter_entries().map(|(idx, val)| (idx, *val)).eq([(0, 10), (5, 60), (6, 600), (31, 312)]));
+
+        let mut fresh = Block32::<u32>::default();
+        fresh.apply_or_insert_pairwise(&disjoint, |_, _, _| unreachable!(), |idx, d| idx as u32 + u32::from(*d));
+        assert!(fresh.iter_entries().map(|(idx, val)| (idx, *val)).eq([(1, 2), (2, 4)]));
+    }
+
+    #[test]
+    fn from_index_closure() {
+        assert!(Block64::<u8>::from_fn(|_| None).is_empty());
+        assert!(Block64::from_fn(Some).iter_entries().all(|(idx, val)| idx == *val));
+        assert!(Block128::from_fn(|_| Some(())).is_full());
+
+        let block = Block16::from_fn(|idx| (idx % 3 == 0).then_some(idx * 10));
+        assert_eq!(block.occupied_mask(), 0b1001_0010_0100_1001);
+        assert_eq!(block.get(15), Some(&150));
+    }
+
+    #[test]
+    fn splatted_blocks() {
+        use core::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>);
+        impl Clone for Counted<'_> {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                Self(self.0)
+            }
+        }
+
+        let clones = Cell::new(0);
+        let block = Block64::splat(Counted(&clones));
+        assert!(block.is_full());
+        assert_eq!(clones.get(), 63);
+
+        const ONES: Block128<u8> = Block128::from_elem(1);
+        assert!(ONES.is_full());
+        assert_eq!(ONES.iter().map(|&val| u32::from(val)).sum::<u32>(), 128);
+    }
+
+    #[test]
+    fn single_entry_statics() {
+        static HANDLER: Block32<fn(u8) -> u8> = Block32::single(3, u8::reverse_bits);
+        assert_eq!(HANDLER.occupied_mask(), 1 << 3);
+        assert_eq!(HANDLER.get(3).map(|handler| handler(1)), Some(0x80));
+        assert!(HANDLER.get(0).is_none());
+
+        static LAST: Block128<u16> = Block128::single(127, 42);
+        assert_eq!(LAST.get(127), Some(&42));
+        assert_eq!(LAST.len(), 1);
+    }
+
+    #[test]
+    fn static_interrupt_table() {
+        struct Handler {
+            name: &'static str,
+            priority: u8,
+        }
+
+        static TABLE: Block32<Handler> = Block32::from_pairs([
+            (0, Handler { name: "reset", priority: 0 }),
+            (7, Handler { name: "timer", priority: 2 }),
+            (31, Handler { name: "uart", priority: 5 }),
+        ]);
+
+        assert_eq!(TABLE.occupied_mask(), 1 << 0 | 1 << 7 | 1 << 31);
+        assert_eq!(TABLE.get(7).map(|handler| handler.name), Some("timer"));
+        assert_eq!(TABLE.get(31).map(|handler| handler.priority), Some(5));
+        assert!(TABLE.get(1).is_none());
+    }
+
+    #[test]
+    fn from_mask_and_closure() {
+        let mut calls = 0;
+        let mask = 0b1000_0000_0010_0110;
+        let block = Block16::from_mask_and_fn(mask, |idx| {
+            calls += 1;
+            idx * 2
+        });
+        assert_eq!(calls, mask.count_ones());
+        assert_eq!(block.occupied_mask(), mask);
+        assert!(block.iter_entries().all(|(idx, &val)| val == idx * 2));
+
+        assert!(Block128::from_mask_and_fn(0, |_| unreachable!()).is_empty());
+        assert!(Block128::from_mask_and_fn(u128::MAX, |_| ()).is_full());
+    }
+
+    #[test]
+    fn static_arrays() {
+        macro_rules! check_statics {
+            ($($block:ident $len:literal)*) => {$({
+                static FULL: $block<usize> = $block::from_array({
+                    let mut vals = [0; $len];
+                    let mut i = 0;
+                    while i < $len {
+                        vals[i] = i * 2;
+                        i += 1;
+                    }
+                    vals
+                });
+                assert!(FULL.is_full());
+                assert!(FULL.iter_entries().all(|(idx, &val)| val == idx * 2));
+
+                static SPARSE: $block<&str> = $block::from_options({
+                    let mut vals = [None; $len];
+                    vals[0] = Some("first");
+                    vals[$len - 1] = Some("last");
+                    vals
+                });
+                assert_eq!(SPARSE.occupied_mask(), 1 | 1 << ($len - 1));
+                assert_eq!(SPARSE.get_many([0, $len - 1]), [Some(&"first"), Some(&"last")]);
+            })*};
+        }
+
+        check_statics!(Block8 8 Block16 16 Block32 32 Block64 64 Block128 128);
+    }
 }
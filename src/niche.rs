@@ -0,0 +1,236 @@
+//! Blocks which encode vacancy with a sentinel value instead of a bit mask.
+//! See [`NicheBlock`](NicheBlock) for more details.
+
+use core::{
+    fmt,
+    iter::FusedIterator,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32,
+        NonZeroU64, NonZeroU8, NonZeroUsize,
+    },
+    slice,
+};
+
+/// Payload types with a spare bit pattern (i.e. a niche) that may encode vacancy.
+///
+/// # Safety
+/// Implementors must uphold the following invariants:
+/// * `Self` and [`Raw`](Self::Raw) have the same size and alignment.
+/// * No valid value of `Self` has the same bit pattern as [`VACANT`](Self::VACANT).
+/// * Every raw value other than `VACANT` is a valid bit pattern of `Self`.
+/// * [`into_raw`](Self::into_raw) and [`from_raw`](Self::from_raw) are plain bit reinterpretations.
+pub unsafe trait NicheValue: Copy {
+    /// The underlying representation of the payload.
+    type Raw: Copy + Eq;
+
+    /// The sentinel bit pattern which encodes a vacant slot.
+    const VACANT: Self::Raw;
+
+    /// Converts the value into its raw representation, which is never [`VACANT`](Self::VACANT).
+    fn into_raw(self) -> Self::Raw;
+
+    /// Converts the raw representation back into a value.
+    ///
+    /// # Safety
+    /// The `raw` value **must not** be [`VACANT`](Self::VACANT).
+    unsafe fn from_raw(raw: Self::Raw) -> Self;
+}
+
+macro_rules! impl_niche_value {
+    ($($nonzero:ty => $raw:ty)*) => {$(
+        // SAFETY: The non-zero integers are `repr(transparent)` wrappers over
+        // their primitive counterparts, where zero is the only invalid value.
+        unsafe impl NicheValue for $nonzero {
+            type Raw = $raw;
+            const VACANT: Self::Raw = 0;
+
+            fn into_raw(self) -> Self::Raw {
+                self.get()
+            }
+
+            unsafe fn from_raw(raw: Self::Raw) -> Self {
+                <$nonzero>::new_unchecked(raw)
+            }
+        }
+    )*};
+}
+
+impl_niche_value! {
+    NonZeroU8 => u8
+    NonZeroU16 => u16
+    NonZeroU32 => u32
+    NonZeroU64 => u64
+    NonZeroU128 => u128
+    NonZeroUsize => usize
+    NonZeroI8 => i8
+    NonZeroI16 => i16
+    NonZeroI32 => i32
+    NonZeroI64 => i64
+    NonZeroI128 => i128
+    NonZeroIsize => isize
+}
+
+/// A fixed block of `N` optionals which encodes vacancy with the [sentinel](NicheValue::VACANT)
+/// value of the payload rather than a separate bit mask. Hence, a `NicheBlock` occupies exactly
+/// `N * size_of::<T>()` bytes. The trade-off is that [`len`](Self::len) must count the occupied
+/// slots in linear time.
+///
+/// # Example
+///
+/// ```rust
+/// use core::{mem::size_of, num::NonZeroU32};
+/// use option_block::niche::NicheBlock;
+///
+/// let mut block = NicheBlock::<NonZeroU32, 64>::default();
+/// assert_eq!(size_of::<NicheBlock<NonZeroU32, 64>>(), 64 * 4);
+///
+/// let val = NonZeroU32::new(10).unwrap();
+/// assert!(block.insert(3, val).is_none());
+/// assert_eq!(block.get(3), Some(&val));
+/// assert_eq!(block.len(), 1);
+/// ```
+#[derive(Clone, Copy)]
+pub struct NicheBlock<T: NicheValue, const N: usize> {
+    data: [T::Raw; N],
+}
+
+impl<T: NicheValue, const N: usize> Default for NicheBlock<T, N> {
+    fn default() -> Self {
+        Self { data: [T::VACANT; N] }
+    }
+}
+
+impl<T: NicheValue + fmt::Debug, const N: usize> fmt::Debug for NicheBlock<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: NicheValue, const N: usize> IntoIterator for &'a NicheBlock<T, N> {
+    type Item = &'a T;
+    type IntoIter = NicheIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: NicheValue, const N: usize> NicheBlock<T, N> {
+    /// Maximum capacity of the fixed-size block.
+    pub const CAPACITY: usize = N;
+
+    /// Checks whether the item at the `index` is vacant (i.e. contains the sentinel).
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+    pub fn is_vacant(&self, index: usize) -> bool {
+        self.data[index] == T::VACANT
+    }
+
+    /// Returns the number of non-null elements in the block. Note that this runs in linear time.
+    pub fn len(&self) -> usize {
+        self.data.iter().filter(|&&raw| raw != T::VACANT).count()
+    }
+
+    /// Returns `true` if the block contains zero elements.
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(|&raw| raw == T::VACANT)
+    }
+
+    /// Attempts to retrieve a shared reference to the element at `index`.
+    /// Returns `None` if the slot is vacant.
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let raw = &self.data[index];
+        if *raw == T::VACANT {
+            None
+        } else {
+            // SAFETY: The slot is not vacant, so it is a valid bit pattern of `T`,
+            // which shares the same layout as `T::Raw` per the trait contract.
+            Some(unsafe { &*(raw as *const T::Raw).cast::<T>() })
+        }
+    }
+
+    /// Attempts to retrieve an exclusive reference to the element at `index`.
+    /// Returns `None` if the slot is vacant.
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let raw = &mut self.data[index];
+        if *raw == T::VACANT {
+            None
+        } else {
+            // SAFETY: The slot is not vacant, so it is a valid bit pattern of `T`,
+            // which shares the same layout as `T::Raw` per the trait contract. Since
+            // no valid `T` is the sentinel, writes through this reference cannot
+            // accidentally vacate the slot.
+            Some(unsafe { &mut *(raw as *mut T::Raw).cast::<T>() })
+        }
+    }
+
+    /// Inserts the `val` at the `index`. If a value already exists, it returns `Some`
+    /// containing the old value. Otherwise, it returns `None`.
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+    pub fn insert(&mut self, index: usize, val: T) -> Option<T> {
+        let raw = core::mem::replace(&mut self.data[index], val.into_raw());
+        if raw == T::VACANT {
+            None
+        } else {
+            // SAFETY: We have already verified that the old value is not the sentinel.
+            Some(unsafe { T::from_raw(raw) })
+        }
+    }
+
+    /// Removes the value at the `index`. If a value already exists, it returns `Some`
+    /// containing that value. Otherwise, it returns `None`.
+    ///
+    /// # Panic
+    /// Panics if `index >= CAPACITY`. See the [maximum capacity](Self::CAPACITY).
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let raw = core::mem::replace(&mut self.data[index], T::VACANT);
+        if raw == T::VACANT {
+            None
+        } else {
+            // SAFETY: We have already verified that the old value is not the sentinel.
+            Some(unsafe { T::from_raw(raw) })
+        }
+    }
+
+    /// Create a by-reference iterator for this block.
+    pub fn iter(&self) -> NicheIter<'_, T> {
+        NicheIter { data: self.data.iter() }
+    }
+}
+
+/// By-reference iterator that borrows from a [`NicheBlock`](NicheBlock).
+pub struct NicheIter<'a, T: NicheValue> {
+    data: slice::Iter<'a, T::Raw>,
+}
+
+impl<'a, T: NicheValue + 'a> Iterator for NicheIter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let raw = self.data.find(|&&raw| raw != T::VACANT)?;
+        // SAFETY: The slot is not vacant, so it is a valid bit pattern of `T`,
+        // which shares the same layout as `T::Raw` per the trait contract.
+        Some(unsafe { &*(raw as *const T::Raw).cast::<T>() })
+    }
+}
+
+impl<'a, T: NicheValue + 'a> FusedIterator for NicheIter<'a, T> {}
+
+impl<T: NicheValue> Clone for NicheIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { data: self.data.clone() }
+    }
+}
+
+impl<T: NicheValue> fmt::Debug for NicheIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NicheIter").field("slots", &self.data.len()).finish()
+    }
+}
@@ -0,0 +1,80 @@
+//! Formatting helpers for the occupancy mask of a block. See the `occupancy` method of
+//! each block variant.
+//!
+//! # Example
+//!
+//! ```rust
+//! let block: option_block::Block8<_> = [(0, ()), (3, ()), (6, ()), (7, ())].into_iter().collect();
+//! let occupancy = block.occupancy();
+//! assert_eq!(occupancy.to_string(), "10010011");
+//! assert_eq!(format!("{occupancy:#04x}"), "0xc9");
+//! ```
+
+use core::fmt;
+
+/// Snapshot of the occupancy mask of a block. The [`Display`](fmt::Display),
+/// [`Debug`](fmt::Debug), and [`Binary`](fmt::Binary) implementations render a fixed-width
+/// bit string with index `0` on the left. The hexadecimal implementations render the raw mask.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Occupancy {
+    mask: u128,
+    width: u32,
+}
+
+impl Occupancy {
+    pub(crate) const fn new(mask: u128, width: u32) -> Self {
+        Self { mask, width }
+    }
+
+    /// Returns the raw mask (widened into a `u128`).
+    pub const fn mask(&self) -> u128 {
+        self.mask
+    }
+
+    /// Returns the number of slots (i.e. the width of the bit string).
+    pub const fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn write_bits(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [b'0'; 128];
+        let bits = &mut buf[..self.width as usize];
+        for (idx, bit) in bits.iter_mut().enumerate() {
+            if self.mask & (1 << idx) != 0 {
+                *bit = b'1';
+            }
+        }
+        // SAFETY: The buffer only ever contains ASCII digits.
+        f.pad(unsafe { core::str::from_utf8_unchecked(bits) })
+    }
+}
+
+impl fmt::Display for Occupancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_bits(f)
+    }
+}
+
+impl fmt::Debug for Occupancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_bits(f)
+    }
+}
+
+impl fmt::Binary for Occupancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_bits(f)
+    }
+}
+
+impl fmt::LowerHex for Occupancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.mask, f)
+    }
+}
+
+impl fmt::UpperHex for Occupancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.mask, f)
+    }
+}
@@ -0,0 +1,109 @@
+//! Raw byte conversions for blocks of plain-old-data (via [`bytemuck`]). This is mainly
+//! useful for persisting a whole block (e.g. to flash) and restoring it later.
+//!
+//! Since vacant slots (and any padding) are uninitialized, a block cannot be viewed as bytes
+//! in place. Instead, the byte representation is laid out as the little-endian mask followed
+//! by every slot in index order, with vacant slots written as zeroed bytes. Exactly [`byte_len`](crate::Block8::byte_len) bytes are always used.
+//!
+//! # Example
+//!
+//! ```rust
+//! use option_block::Block8;
+//!
+//! let block: Block8<u16> = [(1, 0xabcd), (7, 1)].into_iter().collect();
+//! let mut bytes = [0xff; Block8::<u16>::byte_len()];
+//! block.write_bytes(&mut bytes).unwrap();
+//! assert_eq!(bytes[..5], [0b1000_0010, 0, 0, 0xcd, 0xab]);
+//!
+//! let restored = Block8::<u16>::from_bytes(&bytes).unwrap();
+//! assert_eq!(restored, block);
+//! assert!(Block8::<u16>::from_bytes(&bytes[1..]).is_err());
+//! ```
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use bytemuck::{Pod, Zeroable};
+use core::{
+    fmt,
+    mem::{size_of, MaybeUninit},
+};
+
+/// Error returned when a byte buffer does not have exactly the expected length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteLengthError {
+    /// The exact number of bytes that the block representation requires.
+    pub expected: usize,
+    /// The number of bytes that were actually given.
+    pub found: usize,
+}
+
+impl fmt::Display for ByteLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected exactly {} bytes but found {}", self.expected, self.found)
+    }
+}
+
+macro_rules! impl_pod {
+    ($($name:ident $int:ty)*) => {$(
+        impl<T: Zeroable> $name<T> {
+            /// Creates an empty block whose backing storage is entirely zeroed. The slots
+            /// are still considered vacant.
+            pub fn zeroed() -> Self {
+                Self {
+                    // SAFETY: An array of `MaybeUninit` is valid for any bit pattern.
+                    data: unsafe { MaybeUninit::zeroed().assume_init() },
+                    mask: 0,
+                }
+            }
+        }
+
+        impl<T: Pod> $name<T> {
+            /// Returns the exact number of bytes used by the raw byte representation.
+            pub const fn byte_len() -> usize {
+                size_of::<$int>() + Self::CAPACITY as usize * size_of::<T>()
+            }
+
+            /// Writes the raw byte representation (see the [module docs](crate::pod)) into
+            /// `out`, which must be exactly [`byte_len`](Self::byte_len) bytes long.
+            pub fn write_bytes(&self, out: &mut [u8]) -> Result<(), ByteLengthError> {
+                let expected = Self::byte_len();
+                if out.len() != expected {
+                    return Err(ByteLengthError { expected, found: out.len() });
+                }
+
+                let (mask, slots) = out.split_at_mut(size_of::<$int>());
+                mask.copy_from_slice(&self.mask.to_le_bytes());
+                for idx in 0..Self::CAPACITY as usize {
+                    let chunk = &mut slots[idx * size_of::<T>()..][..size_of::<T>()];
+                    match self.get(idx) {
+                        Some(val) => chunk.copy_from_slice(bytemuck::bytes_of(val)),
+                        None => chunk.fill(0),
+                    }
+                }
+                Ok(())
+            }
+
+            /// Restores a block from its raw byte representation. The `bytes` need not be
+            /// aligned, but must be exactly [`byte_len`](Self::byte_len) bytes long. The bytes
+            /// of vacant slots are copied but never read as values.
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, ByteLengthError> {
+                let expected = Self::byte_len();
+                if bytes.len() != expected {
+                    return Err(ByteLengthError { expected, found: bytes.len() });
+                }
+
+                let (mask, slots) = bytes.split_at(size_of::<$int>());
+                let mut block = Self::default();
+                for idx in 0..Self::CAPACITY as usize {
+                    let chunk = &slots[idx * size_of::<T>()..][..size_of::<T>()];
+                    block.data[idx] = MaybeUninit::new(bytemuck::pod_read_unaligned(chunk));
+                }
+
+                // NOTE: Every slot has been initialized above, so any mask is valid here.
+                block.mask = <$int>::from_le_bytes(mask.try_into().unwrap());
+                Ok(block)
+            }
+        }
+    )*};
+}
+
+impl_pod!(Block8 u8 Block16 u16 Block32 u32 Block64 u64 Block128 u128);
@@ -0,0 +1,117 @@
+//! Dense [`serde`] representation of the block variants. Each block is encoded as a
+//! fixed-length sequence of exactly `CAPACITY` [`Option`] entries so that every occupied
+//! slot round-trips to the very same index.
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use core::{fmt, marker::PhantomData};
+#[cfg(feature = "postcard")]
+use postcard::experimental::max_size::MaxSize;
+use serde::{
+    de::{self, IgnoredAny, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+macro_rules! impl_serde_dense {
+    ($name:ident) => {
+        impl<T: Serialize> Serialize for $name<T> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut tuple = serializer.serialize_tuple(Self::CAPACITY as usize)?;
+                for idx in 0..Self::CAPACITY as usize {
+                    tuple.serialize_element(&self.get(idx))?;
+                }
+                tuple.end()
+            }
+        }
+
+        impl<'de, T: Deserialize<'de>> Deserialize<'de> for $name<T> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct DenseVisitor<T>(PhantomData<T>);
+
+                impl<'de, T: Deserialize<'de>> Visitor<'de> for DenseVisitor<T> {
+                    type Value = $name<T>;
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(f, "a sequence of exactly {} optional entries", $name::<T>::CAPACITY)
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                        // Should a later element fail, the partially filled block is dropped
+                        // along with the error. Hence, no deserialized element is leaked.
+                        let mut block = $name::default();
+                        for idx in 0..$name::<T>::CAPACITY as usize {
+                            let Some(entry) = seq.next_element::<Option<T>>()? else {
+                                return Err(de::Error::invalid_length(idx, &self));
+                            };
+                            if let Some(val) = entry {
+                                block.insert(idx, val);
+                            }
+                        }
+
+                        if seq.next_element::<IgnoredAny>()?.is_some() {
+                            let len = $name::<T>::CAPACITY as usize + 1;
+                            return Err(de::Error::invalid_length(len, &self));
+                        }
+
+                        Ok(block)
+                    }
+                }
+
+                deserializer.deserialize_tuple(Self::CAPACITY as usize, DenseVisitor(PhantomData))
+            }
+
+            fn deserialize_in_place<D: Deserializer<'de>>(deserializer: D, place: &mut Self) -> Result<(), D::Error> {
+                struct InPlaceVisitor<'a, T>(&'a mut $name<T>);
+
+                impl<'de, T: Deserialize<'de>> Visitor<'de> for InPlaceVisitor<'_, T> {
+                    type Value = ();
+
+                    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        write!(f, "a sequence of exactly {} optional entries", $name::<T>::CAPACITY)
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                        // Each slot is overwritten as soon as its entry arrives (dropping the old
+                        // value, if any). Should a later element fail, the block simply holds a
+                        // mix of new and old entries, all of which are still properly tracked.
+                        for idx in 0..$name::<T>::CAPACITY as usize {
+                            let Some(entry) = seq.next_element::<Option<T>>()? else {
+                                return Err(de::Error::invalid_length(idx, &self));
+                            };
+                            self.0.set(idx, entry);
+                        }
+
+                        if seq.next_element::<IgnoredAny>()?.is_some() {
+                            let len = $name::<T>::CAPACITY as usize + 1;
+                            return Err(de::Error::invalid_length(len, &self));
+                        }
+
+                        Ok(())
+                    }
+                }
+
+                deserializer.deserialize_tuple(Self::CAPACITY as usize, InPlaceVisitor(place))
+            }
+        }
+    };
+}
+
+impl_serde_dense!(Block8);
+impl_serde_dense!(Block16);
+impl_serde_dense!(Block32);
+impl_serde_dense!(Block64);
+impl_serde_dense!(Block128);
+
+/// Upper bound on the size of the dense encoding under [`postcard`]. Since the dense encoding
+/// is a tuple of `CAPACITY` optional entries, no length prefix is ever emitted.
+#[cfg(feature = "postcard")]
+macro_rules! impl_max_size {
+    ($($name:ident)*) => {$(
+        impl<T: MaxSize> MaxSize for $name<T> {
+            const POSTCARD_MAX_SIZE: usize = $name::<T>::CAPACITY as usize * Option::<T>::POSTCARD_MAX_SIZE;
+        }
+    )*};
+}
+
+#[cfg(feature = "postcard")]
+impl_max_size!(Block8 Block16 Block32 Block64 Block128);
@@ -0,0 +1,147 @@
+//! Sparse [`serde`] representation of the block variants, meant to be used as a field
+//! attribute: `#[serde(with = "option_block::serde_sparse")]`. Only the occupied slots are
+//! encoded as `(index, value)` pairs, so a mostly empty `Block128` stays small on the wire.
+//!
+//! Human-readable formats (e.g. JSON) receive a map from indices to values. Compact formats
+//! (e.g. postcard) receive a sequence of `(index, value)` tuples instead.
+//!
+//! Deserialization rejects out-of-range indices as well as duplicate indices. To let later
+//! duplicates silently overwrite earlier ones, use the [`last_wins`] module instead.
+//!
+//! # Example
+//!
+//! ```rust
+//! use option_block::Block128;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Inventory {
+//!     #[serde(with = "option_block::serde_sparse")]
+//!     slots: Block128<u32>,
+//! }
+//!
+//! let inventory = Inventory { slots: [(3, 30), (100, 1000)].into_iter().collect() };
+//! let json = serde_json::to_string(&inventory).unwrap();
+//! assert_eq!(json, r#"{"slots":{"3":30,"100":1000}}"#);
+//! ```
+
+use crate::FixedBlock;
+use core::{fmt, marker::PhantomData};
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Serializes only the occupied slots of the `block` as `(index, value)` pairs.
+pub fn serialize<B, S>(block: &B, serializer: S) -> Result<S::Ok, S::Error>
+where
+    B: FixedBlock,
+    B::Item: Serialize,
+    S: Serializer,
+{
+    let len = block.len() as usize;
+    let entries = (0..B::CAPACITY as usize).filter_map(|idx| Some((idx, block.get(idx)?)));
+    if serializer.is_human_readable() {
+        let mut map = serializer.serialize_map(Some(len))?;
+        for (idx, val) in entries {
+            map.serialize_entry(&idx, val)?;
+        }
+        map.end()
+    } else {
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        for entry in entries {
+            seq.serialize_element(&entry)?;
+        }
+        seq.end()
+    }
+}
+
+/// Reconstructs a block from its `(index, value)` pairs. Duplicate indices are rejected.
+pub fn deserialize<'de, B, D>(deserializer: D) -> Result<B, D::Error>
+where
+    B: FixedBlock,
+    B::Item: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserialize_with(deserializer, false)
+}
+
+/// Same as the parent module, except that later duplicate indices overwrite earlier ones.
+pub mod last_wins {
+    pub use super::serialize;
+    use crate::FixedBlock;
+    use serde::{Deserialize, Deserializer};
+
+    /// Reconstructs a block from its `(index, value)` pairs. Later duplicates win.
+    pub fn deserialize<'de, B, D>(deserializer: D) -> Result<B, D::Error>
+    where
+        B: FixedBlock,
+        B::Item: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        super::deserialize_with(deserializer, true)
+    }
+}
+
+fn deserialize_with<'de, B, D>(deserializer: D, allow_duplicates: bool) -> Result<B, D::Error>
+where
+    B: FixedBlock,
+    B::Item: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    struct SparseVisitor<B> {
+        allow_duplicates: bool,
+        block: PhantomData<B>,
+    }
+
+    impl<B: FixedBlock> SparseVisitor<B> {
+        /// Inserts a single entry. On error, the partially filled block is dropped by the
+        /// caller along with all of its (already deserialized) elements.
+        fn insert<E: de::Error>(&self, block: &mut B, idx: usize, val: B::Item) -> Result<(), E> {
+            if idx >= B::CAPACITY as usize {
+                let msg = "an index less than the capacity of the block";
+                return Err(E::invalid_value(de::Unexpected::Unsigned(idx as u64), &msg));
+            }
+            if block.insert(idx, val).is_some() && !self.allow_duplicates {
+                return Err(E::custom(format_args!("duplicate index {idx}")));
+            }
+            Ok(())
+        }
+    }
+
+    impl<'de, B> Visitor<'de> for SparseVisitor<B>
+    where
+        B: FixedBlock,
+        B::Item: Deserialize<'de>,
+    {
+        type Value = B;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a collection of (index, value) pairs")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut block = B::default();
+            while let Some((idx, val)) = map.next_entry()? {
+                self.insert(&mut block, idx, val)?;
+            }
+            Ok(block)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut block = B::default();
+            while let Some((idx, val)) = seq.next_element()? {
+                self.insert(&mut block, idx, val)?;
+            }
+            Ok(block)
+        }
+    }
+
+    let visitor = SparseVisitor { allow_duplicates, block: PhantomData };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_map(visitor)
+    } else {
+        deserializer.deserialize_seq(visitor)
+    }
+}
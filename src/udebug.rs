@@ -0,0 +1,22 @@
+//! [`ufmt::uDebug`] implementations for the block variants. Only the occupied slots are
+//! written, as a map from slot index to value (e.g. `{0: 12, 5: 99}`). Unlike the regular
+//! `Debug` implementation, nothing here touches `core::fmt`.
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use ufmt::{uDebug, uWrite, Formatter};
+
+macro_rules! impl_udebug {
+    ($($name:ident)*) => {$(
+        impl<T: uDebug> uDebug for $name<T> {
+            fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+                let mut map = f.debug_map()?;
+                for (idx, val) in self.iter_entries() {
+                    map.entry(&idx, val)?;
+                }
+                map.finish()
+            }
+        }
+    )*};
+}
+
+impl_udebug!(Block8 Block16 Block32 Block64 Block128);
@@ -0,0 +1,79 @@
+//! Raw byte conversions for blocks of [`zerocopy`]-compatible types. The byte layout is packed:
+//! the little-endian occupancy mask (`CAPACITY / 8` bytes) is immediately followed by every slot
+//! in index order (`CAPACITY * size_of::<T>()` bytes), with vacant slots written as zeroed bytes.
+//! There is no padding in between, even if `T` is aligned. For example, a `Block8<u64>` always
+//! takes exactly `1 + 8 * 8 = 65` bytes.
+//!
+//! Note that this deliberately deviates from a `#[repr(C)]` overlay of the block itself. Since
+//! vacant slots (and any padding) are uninitialized `MaybeUninit` storage, the block can never be
+//! reinterpreted as bytes in place. Thus, the bytes are always copied slot by slot, in which case
+//! the padding of a `#[repr(C)]` struct (e.g. `72` bytes for a `Block8<u64>`) would only be waste.
+//! This is the same layout as the one used by the `bytemuck` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use option_block::Block8;
+//!
+//! let block: Block8<u64> = [(0, u64::MAX), (7, 1)].into_iter().collect();
+//! let mut packet = [0xee; 1 + 8 * 8];
+//! block.write_to_bytes(&mut packet).unwrap();
+//! assert_eq!(packet[..9], [0b1000_0001, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+//! assert_eq!(Block8::<u64>::read_from_bytes(&packet), Some(block));
+//! ```
+
+use crate::{Block128, Block16, Block32, Block64, Block8};
+use core::mem::{size_of, MaybeUninit};
+use zerocopy::{FromBytes, Immutable, IntoBytes};
+
+macro_rules! impl_zerocopy {
+    ($($name:ident $int:ty)*) => {$(
+        impl<T: FromBytes + IntoBytes + Immutable> $name<T> {
+            /// Reads a block from its raw byte representation: the little-endian mask immediately
+            /// followed by every slot in index order, without any padding (see the
+            /// [module-level layout](crate::zerocopy_bytes)). Returns `None` if `bytes` is not exactly
+            /// the size of the layout. Any mask is accepted: every slot is read from `bytes`, so no
+            /// occupied slot can ever refer to uninitialized memory.
+            pub fn read_from_bytes(bytes: &[u8]) -> Option<Self> {
+                if bytes.len() != size_of::<$int>() + Self::CAPACITY as usize * size_of::<T>() {
+                    return None;
+                }
+
+                let (mask, slots) = bytes.split_at(size_of::<$int>());
+                let mut block = Self::default();
+                for idx in 0..Self::CAPACITY as usize {
+                    let chunk = &slots[idx * size_of::<T>()..][..size_of::<T>()];
+                    block.data[idx] = MaybeUninit::new(T::read_from_bytes(chunk).ok()?);
+                }
+
+                // NOTE: Every slot has been initialized above, so any mask is valid here.
+                block.mask = <$int>::from_le_bytes(mask.try_into().ok()?);
+                Some(block)
+            }
+
+            /// Writes the raw byte representation into `out`: the little-endian mask immediately
+            /// followed by every slot in index order, without any padding (see the
+            /// [module-level layout](crate::zerocopy_bytes)). Vacant slots are written as zeroed bytes
+            /// so that the output is deterministic. Returns `None` if `out` is not exactly the size
+            /// of the layout.
+            pub fn write_to_bytes(&self, out: &mut [u8]) -> Option<()> {
+                if out.len() != size_of::<$int>() + Self::CAPACITY as usize * size_of::<T>() {
+                    return None;
+                }
+
+                let (mask, slots) = out.split_at_mut(size_of::<$int>());
+                mask.copy_from_slice(&self.mask.to_le_bytes());
+                for idx in 0..Self::CAPACITY as usize {
+                    let chunk = &mut slots[idx * size_of::<T>()..][..size_of::<T>()];
+                    match self.get(idx) {
+                        Some(val) => chunk.copy_from_slice(val.as_bytes()),
+                        None => chunk.fill(0),
+                    }
+                }
+                Some(())
+            }
+        }
+    )*};
+}
+
+impl_zerocopy!(Block8 u8 Block16 u16 Block32 u32 Block64 u64 Block128 u128);
@@ -0,0 +1,73 @@
+//! This test module checks the native `bincode` encoding of the block variants.
+#![cfg(feature = "bincode")]
+
+use bincode::{
+    borrow_decode_from_slice, config, de::Decoder, decode_from_slice, encode_to_vec, error::DecodeError, Decode,
+};
+use option_block::{Block128, Block16, Block8};
+use std::rc::Rc;
+
+#[test]
+fn round_trips() {
+    let config = config::standard();
+
+    let empty = Block128::<u64>::default();
+    let bytes = encode_to_vec(&empty, config).unwrap();
+    assert_eq!(bytes, [0]);
+    assert_eq!(decode_from_slice::<Block128<u64>, _>(&bytes, config).unwrap(), (empty, 1));
+
+    let sparse: Block16<u32> = [(3, 300), (15, 7)].into_iter().collect();
+    let bytes = encode_to_vec(&sparse, config).unwrap();
+    assert_eq!(decode_from_slice::<Block16<u32>, _>(&bytes, config).unwrap().0, sparse);
+
+    let full = Block8::from([0u8, 1, 2, 3, 4, 5, 6, 7]);
+    let bytes = encode_to_vec(&full, config).unwrap();
+    assert_eq!(bytes.len(), 1 + 8);
+    assert_eq!(decode_from_slice::<Block8<u8>, _>(&bytes, config).unwrap().0, full);
+
+    let full = Block128::from(core::array::from_fn(|i| i as u16 * 500));
+    let bytes = encode_to_vec(&full, config::legacy()).unwrap();
+    assert_eq!(decode_from_slice::<Block128<u16>, _>(&bytes, config::legacy()).unwrap().0, full);
+
+    let words: Block8<&str> = [(1, "hello"), (6, "world")].into_iter().collect();
+    let bytes = encode_to_vec(&words, config).unwrap();
+    assert_eq!(borrow_decode_from_slice::<Block8<&str>, _>(&bytes, config).unwrap().0, words);
+}
+
+#[test]
+fn missing_values_are_rejected() {
+    let config = config::standard();
+    let sparse: Block8<u8> = [(0, 1), (2, 3), (4, 5)].into_iter().collect();
+    let bytes = encode_to_vec(&sparse, config).unwrap();
+    for len in 0..bytes.len() {
+        assert!(decode_from_slice::<Block8<u8>, _>(&bytes[..len], config).is_err());
+    }
+}
+
+/// Decodes into a shared resource so that leaks can be detected.
+struct Tracked(#[allow(dead_code)] Rc<()>);
+
+thread_local! {
+    static RESOURCE: Rc<()> = Rc::new(());
+}
+
+impl<Context> Decode<Context> for Tracked {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        u8::decode(decoder)?;
+        Ok(Self(RESOURCE.with(Rc::clone)))
+    }
+}
+
+#[test]
+fn failed_decoding_drops_values() {
+    let config = config::standard();
+    let block = Block8::from([9u8; 8]);
+    let bytes = encode_to_vec(&block, config).unwrap();
+
+    let (decoded, _) = decode_from_slice::<Block8<Tracked>, _>(&bytes, config).unwrap();
+    assert_eq!(RESOURCE.with(Rc::strong_count), 9);
+    drop(decoded);
+
+    assert!(decode_from_slice::<Block8<Tracked>, _>(&bytes[..6], config).is_err());
+    assert_eq!(RESOURCE.with(Rc::strong_count), 1);
+}
@@ -0,0 +1,14 @@
+//! Shared utilities for the integration tests.
+
+/// Minimal deterministic pseudo-random generator (xorshift) for reproducible tests.
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
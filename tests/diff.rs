@@ -0,0 +1,73 @@
+//! This test module checks that the change records produced by `diff` may be
+//! replayed via `apply_diff` to replicate the contents of another block.
+
+mod common;
+
+use common::XorShift;
+use option_block::{
+    diff::{Change, ChangeOwned, DiffMismatch},
+    Block128, Block8,
+};
+
+fn random_block(rng: &mut XorShift) -> Block128<u8> {
+    let mask = u128::from(rng.next()) << 64 | u128::from(rng.next());
+    (0..128).filter(|i| mask & (1 << i) != 0).map(|i| (i, rng.next() as u8 % 4)).collect()
+}
+
+#[test]
+fn randomized_round_trip() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    for _ in 0..256 {
+        let old = random_block(&mut rng);
+        let new = random_block(&mut rng);
+
+        let mut copy = old.clone();
+        let count = old.diff(&new).count();
+        assert_eq!(copy.apply_diff(old.diff(&new).map(Change::to_owned)), Ok(count));
+
+        for i in 0..Block128::<u8>::CAPACITY as usize {
+            assert_eq!(copy.get(i), new.get(i));
+        }
+
+        assert_eq!(copy.diff(&new).count(), 0);
+    }
+}
+
+#[test]
+fn mismatched_changes() {
+    let mut block: Block8<_> = [(0, 'a'), (1, 'b')].into_iter().collect();
+
+    let err = block.apply_diff([ChangeOwned::Modified(0, 'x'), ChangeOwned::Added(1, 'y')]).unwrap_err();
+    assert_eq!(err, DiffMismatch { applied: 1, change: ChangeOwned::Added(1, 'y') });
+    assert_eq!(block.get(0), Some(&'x'));
+    assert_eq!(block.get(1), Some(&'b'));
+
+    let err = block.apply_diff([ChangeOwned::Removed(2)]).unwrap_err();
+    assert_eq!(err, DiffMismatch { applied: 0, change: ChangeOwned::Removed(2) });
+
+    let err = block.apply_diff([ChangeOwned::Modified(3, 'z')]).unwrap_err();
+    assert_eq!(err, DiffMismatch { applied: 0, change: ChangeOwned::Modified(3, 'z') });
+
+    let err = block.apply_diff([ChangeOwned::Added(8, 'z')]).unwrap_err();
+    assert_eq!(err, DiffMismatch { applied: 0, change: ChangeOwned::Added(8, 'z') });
+
+    assert_eq!(block.len(), 2);
+    assert_eq!(block.get(0), Some(&'x'));
+    assert_eq!(block.get(1), Some(&'b'));
+}
+
+#[test]
+fn rejected_change_is_not_dropped() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    assert!(block.insert(0, resource.clone()).is_none());
+
+    let err = block.apply_diff([ChangeOwned::Added(0, resource.clone())]).unwrap_err();
+    assert_eq!(Rc::strong_count(&resource), 3);
+    drop(err);
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    assert_eq!(block.apply_diff([ChangeOwned::Removed(0)]), Ok(1));
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
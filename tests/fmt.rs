@@ -0,0 +1,115 @@
+//! This test module checks the formatted output of the block variants.
+
+use option_block::{any::AnyBlock, niche::NicheBlock, Block, Block128, Block8};
+
+#[test]
+fn debug_shows_occupied_slots_only() {
+    assert_eq!(format!("{:?}", Block8::<&str>::default()), "Block8 {}");
+
+    let sparse: Block8<_> = [(1, "a"), (5, "b")].into_iter().collect();
+    assert_eq!(format!("{sparse:?}"), r#"Block8 {1: "a", 5: "b"}"#);
+    assert_eq!(format!("{sparse:#?}"), "Block8 {\n    1: \"a\",\n    5: \"b\",\n}");
+
+    let full = Block8::from([0, 1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(format!("{full:?}"), "Block8 {0: 0, 1: 1, 2: 2, 3: 3, 4: 4, 5: 5, 6: 6, 7: 7}");
+
+    let wide: Block128<u8> = [(127, 1)].into_iter().collect();
+    assert_eq!(format!("{wide:?}"), "Block128 {127: 1}");
+
+    let exact: Block<char, 11> = [(10, 'z')].into_iter().collect();
+    assert_eq!(format!("{exact:?}"), "Block {10: 'z'}");
+}
+
+#[test]
+fn occupancy_bit_strings() {
+    struct Opaque;
+
+    let block: Block8<Opaque> = [(0, Opaque), (3, Opaque), (6, Opaque), (7, Opaque)].into_iter().collect();
+    let occupancy = block.occupancy();
+    assert_eq!(occupancy.to_string(), "10010011");
+    assert_eq!(format!("{occupancy:?}"), "10010011");
+    assert_eq!(format!("{occupancy:b}"), "10010011");
+    assert_eq!(format!("{occupancy:x}"), "c9");
+    assert_eq!(format!("{occupancy:#06X}"), "0x00C9");
+    assert_eq!(format!("[{occupancy:>10}]"), "[  10010011]");
+
+    assert_eq!(Block8::<Opaque>::default().occupancy().to_string(), "00000000");
+
+    let wide: Block128<Opaque> = [(0, Opaque), (127, Opaque)].into_iter().collect();
+    let bits = wide.occupancy().to_string();
+    assert_eq!(bits.len(), 128);
+    assert!(bits.starts_with("10") && bits.ends_with("01"));
+    assert_eq!(bits.matches('1').count(), 2);
+    assert_eq!(format!("{:x}", wide.occupancy()), "80000000000000000000000000000001");
+
+    let full = option_block::Block16::from([(); 16]);
+    assert_eq!(full.occupancy().to_string(), "1".repeat(16));
+    assert_eq!(full.occupancy().width(), 16);
+}
+
+#[test]
+fn occupied_error_messages() {
+    let mut block = Block8::<u8>::default();
+    block.insert(2, 20);
+    let err = block.try_insert(2, 30).unwrap_err();
+    assert_eq!(err.to_string(), "slot 2 is already occupied");
+    assert_eq!(format!("{err:?}"), "OccupiedError { index: 2, existing: 20, value: 30 }");
+}
+
+#[test]
+fn index_out_of_range_messages() {
+    let mut block = Block8::<u8>::default();
+    let (err, _) = block.checked_insert(9, 0).unwrap_err();
+    assert_eq!(err.to_string(), "index 9 is out of range for a block of capacity 8");
+    assert_eq!(format!("{err:?}"), "IndexOutOfRange { index: 9, capacity: 8 }");
+}
+
+#[test]
+fn exact_iterators_show_remaining_mask() {
+    struct Opaque;
+
+    let block: Block<Opaque, 11> = [(1, Opaque), (4, Opaque), (10, Opaque)].into_iter().collect();
+    let mut iter = block.iter();
+    iter.next();
+    assert_eq!(format!("{iter:?}"), "BlockIter { mask: 0b10000010000, len: 2 }");
+    assert_eq!(format!("{:?}", block.iter_entries()), "BlockEntries { mask: 0b10000010010, len: 3 }");
+    assert_eq!(format!("{:?}", block.occupied_indices()), "BlockIndices { mask: 1042 }");
+}
+
+#[test]
+fn remaining_iterators_are_debug() {
+    struct Opaque;
+
+    let mut block: Block8<Opaque> = [(1, Opaque), (4, Opaque), (6, Opaque)].into_iter().collect();
+    {
+        let mut extract = block.extract_if(|idx, _| idx == 1);
+        assert!(extract.next().is_some());
+        assert_eq!(format!("{extract:?}"), "Block8ExtractIf { mask: 0b1010000 }");
+    }
+
+    let mut slots = block.slots_mut();
+    slots.nth(4);
+    assert_eq!(format!("{slots:?}"), "Block8SlotsMut { mask: 0b1010000, len: 3 }");
+
+    let mut drain = block.drain();
+    drain.next();
+    assert_eq!(format!("{drain:?}"), "Block8Drain { mask: 0b1000000, len: 1 }");
+    drop(drain);
+
+    let mut values: Block8<u8> = [(2, 20)].into_iter().collect();
+    let slot = values.slots_mut().nth(2).unwrap();
+    assert_eq!(format!("{slot:?}"), "Block8SlotMut { index: 2, value: Some(20) }");
+
+    let any = AnyBlock::from(Block8::<Opaque>::from_pairs([(0, Opaque), (3, Opaque)]));
+    let iter = any.iter();
+    assert_eq!(format!("{:?}", iter.clone()), "Block8(Block8Iter { mask: 0b1001, len: 2 })");
+    assert_eq!(format!("{:?}", any.into_iter()), "Block8(Block8IntoIter { mask: 0b1001, len: 2 })");
+
+    let mut niche = NicheBlock::<core::num::NonZeroU8, 4>::default();
+    niche.insert(1, core::num::NonZeroU8::MIN);
+    assert_eq!(format!("{:?}", niche.iter().clone()), "NicheIter { slots: 4 }");
+
+    let mut exact: Block<Opaque, 11> = [(2, Opaque), (9, Opaque)].into_iter().collect();
+    assert_eq!(format!("{:?}", exact.extract_if(|_, _| false)), "BlockExtractIf { mask: 0b1000000100 }");
+    assert_eq!(format!("{:?}", exact.drain()), "BlockDrain { mask: 0b1000000100, len: 2 }");
+}
@@ -105,3 +105,1380 @@ fn default_getters() {
     assert_eq!(Rc::strong_count(&resource), 1);
     assert_eq!(Rc::strong_count(&other), 1);
 }
+
+#[test]
+fn failed_conversion_drops_everything() {
+    use std::rc::Rc;
+
+    struct Converted(#[allow(dead_code)] Rc<u8>);
+
+    impl TryFrom<Rc<u8>> for Converted {
+        type Error = Rc<u8>;
+        fn try_from(value: Rc<u8>) -> Result<Self, Self::Error> {
+            if *value == 0 {
+                Err(value)
+            } else {
+                Ok(Self(value))
+            }
+        }
+    }
+
+    let good = Rc::new(1);
+    let bad = Rc::new(0);
+    let mut block = Block8::default();
+    assert!(block.insert(1, good.clone()).is_none());
+    assert!(block.insert(3, bad.clone()).is_none());
+    assert!(block.insert(6, good.clone()).is_none());
+    assert_eq!(Rc::strong_count(&good), 3);
+    assert_eq!(Rc::strong_count(&bad), 2);
+
+    let (idx, err) = block.try_convert::<Converted>().err().unwrap();
+    assert_eq!(idx, 3);
+    assert!(Rc::ptr_eq(&err, &bad));
+    assert_eq!(Rc::strong_count(&good), 1);
+    assert_eq!(Rc::strong_count(&bad), 2);
+    drop(err);
+    assert_eq!(Rc::strong_count(&bad), 1);
+}
+
+#[test]
+fn slot_proxies() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [0, 1, 4, 5] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    for mut slot in block.slots_mut() {
+        if slot.index() % 2 == 1 {
+            drop(slot.take());
+        } else {
+            drop(slot.insert(resource.clone()));
+        }
+    }
+
+    assert_eq!(block.len(), 4);
+    assert_eq!(Rc::strong_count(&resource), 5);
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn set_occupancy_combinations() {
+    use option_block::{Block128, Block32};
+    use std::rc::Rc;
+
+    macro_rules! check {
+        ($block:ty) => {{
+            let old = Rc::new(0);
+            let new = Rc::new(1);
+            let mut block = <$block>::default();
+            let last = <$block>::CAPACITY as usize - 1;
+
+            // Vacant to vacant
+            assert!(block.set(last, None).is_none());
+            assert!(block.is_vacant(last));
+
+            // Vacant to occupied
+            assert!(block.set(last, Some(old.clone())).is_none());
+            assert_eq!(Rc::strong_count(&old), 2);
+
+            // Occupied to occupied
+            let prev = block.set(last, Some(new.clone())).unwrap();
+            assert!(Rc::ptr_eq(&prev, &old));
+            drop(prev);
+            assert_eq!(Rc::strong_count(&old), 1);
+            assert_eq!(Rc::strong_count(&new), 2);
+
+            // Occupied to vacant
+            let prev = block.set(last, None).unwrap();
+            assert!(Rc::ptr_eq(&prev, &new));
+            assert!(block.is_empty());
+            drop(prev);
+            assert_eq!(Rc::strong_count(&new), 1);
+        }};
+    }
+
+    check!(Block8<Rc<u8>>);
+    check!(Block32<Rc<u8>>);
+    check!(Block128<Rc<u8>>);
+}
+
+#[test]
+fn shrinking_dense_prefix() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    block.resize_with(6, || resource.clone());
+    assert_eq!(Rc::strong_count(&resource), 7);
+
+    block.resize_with(2, || unreachable!());
+    assert_eq!(Rc::strong_count(&resource), 3);
+    assert_eq!(block.len(), 2);
+
+    block.resize_with(0, || unreachable!());
+    assert_eq!(Rc::strong_count(&resource), 1);
+    assert!(block.is_empty());
+}
+
+#[test]
+fn clone_from_dirty_mask() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let other = Rc::new(1);
+
+    let mut source = Block8::default();
+    let mut target = Block8::default();
+    for i in [0, 1, 2, 3] {
+        assert!(source.insert(i, resource.clone()).is_none());
+    }
+    for i in [2, 3, 4, 5] {
+        assert!(target.insert(i, other.clone()).is_none());
+    }
+
+    let mask = 0b0011_1010;
+    let mut expected = target.clone();
+    for i in 0..Block8::<Rc<u8>>::CAPACITY as usize {
+        if mask & (1 << i) != 0 {
+            drop(expected.set(i, source.get(i).cloned()));
+        }
+    }
+
+    target.clone_from_mask(&source, mask);
+    for i in 0..Block8::<Rc<u8>>::CAPACITY as usize {
+        match (target.get(i), expected.get(i)) {
+            (Some(a), Some(b)) => assert!(Rc::ptr_eq(a, b)),
+            (None, None) => {}
+            _ => panic!("slot {i} differs from the reference loop"),
+        }
+    }
+
+    drop(expected);
+    assert!(target.is_vacant(0));
+    assert!(target.is_vacant(4));
+    assert!(target.is_vacant(5));
+    assert_eq!(Rc::strong_count(&resource), 7);
+    assert_eq!(Rc::strong_count(&other), 2);
+
+    drop(target);
+    drop(source);
+    assert_eq!(Rc::strong_count(&resource), 1);
+    assert_eq!(Rc::strong_count(&other), 1);
+}
+
+#[test]
+fn panicking_map_in_place() {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [0, 2, 4, 6] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        block.map_in_place(|idx, val| {
+            assert_ne!(idx, 4, "injected panic");
+            val
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&resource), 4);
+    assert!(block.is_vacant(4));
+    assert_eq!(block.len(), 3);
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn interleaving_moves_values() {
+    use option_block::Block16;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let mut even = Block8::default();
+    let mut odd = Block8::default();
+    for i in [0, 3, 7] {
+        assert!(even.insert(i, resource.clone()).is_none());
+    }
+    for i in [1, 3] {
+        assert!(odd.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    let wide = Block16::interleave(even, odd);
+    assert_eq!(wide.len(), 5);
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    let (even, odd) = wide.deinterleave();
+    assert_eq!(Rc::strong_count(&resource), 6);
+    drop(even);
+    assert_eq!(Rc::strong_count(&resource), 3);
+    drop(odd);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn moving_into_wider_blocks() {
+    use option_block::Block128;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let mut dest = Block128::default();
+    assert!(dest.insert(1, resource.clone()).is_none());
+    assert!(dest.insert(100, resource.clone()).is_none());
+
+    let mut source = Block8::default();
+    assert!(source.insert(0, resource.clone()).is_none());
+    assert!(source.insert(1, resource.clone()).is_none());
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    // Collision at index 1
+    let mut source = source.move_into(&mut dest).unwrap_err();
+    assert_eq!(source.len(), 2);
+    assert_eq!(dest.len(), 2);
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    // Disjoint transfer
+    assert!(source.remove(1).is_some());
+    assert!(source.insert(7, resource.clone()).is_none());
+    assert_eq!(Rc::strong_count(&resource), 5);
+    source.move_into(&mut dest).unwrap();
+    assert_eq!(dest.len(), 4);
+    assert!(dest.get(0).is_some());
+    assert!(dest.get(7).is_some());
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    // Out of bounds for a narrower destination
+    let mut narrow = Block8::default();
+    let mut wide = Block128::default();
+    assert!(wide.insert(8, resource.clone()).is_none());
+    let wide = wide.move_into(&mut narrow).unwrap_err();
+    assert_eq!(wide.len(), 1);
+    assert!(narrow.is_empty());
+
+    drop(wide);
+    drop(dest);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn clear_partially_occupied() {
+    use option_block::Block128;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let mut block = Block128::default();
+    for i in [0, 5, 64, 127] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    block.clear();
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 1);
+
+    block.clear();
+    assert!(block.is_empty());
+    assert!(block.insert(3, resource.clone()).is_none());
+    assert_eq!(Rc::strong_count(&resource), 2);
+}
+
+#[test]
+fn partial_drain_drops_remaining() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [1, 3, 5, 7] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    let mut drain = block.drain();
+    let (idx, val) = drain.next().unwrap();
+    assert_eq!(idx, 1);
+    assert!(Rc::ptr_eq(&val, &resource));
+    drop(drain);
+
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 2);
+    drop(val);
+    assert_eq!(Rc::strong_count(&resource), 1);
+
+    for i in [0, 4] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert!(block.drain().map(|(idx, _)| idx).eq([0, 4]));
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn leaked_drain_keeps_remaining() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [2, 6] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+
+    let mut drain = block.drain();
+    drop(drain.next());
+    core::mem::forget(drain);
+
+    assert_eq!(block.len(), 1);
+    assert!(block.is_vacant(2));
+    assert!(block.get(6).is_some());
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn partial_into_entries_drops_remaining() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [1, 5, 7] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    let mut entries = block.into_entries();
+    let (idx, first) = entries.next().unwrap();
+    assert_eq!(idx, 1);
+    drop(entries);
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    drop(first);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn partial_into_iter_drops_remaining() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [0, 3, 5, 7] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    let mut iter = block.into_iter();
+    let first = iter.next().unwrap();
+    drop(iter);
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    drop(first);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn reversed_into_iter_drops_remaining() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [1, 2, 4, 6] {
+        assert!(block.insert(i, Rc::new(i)).is_none());
+    }
+    assert!(block.insert(7, resource.clone()).is_none());
+
+    let mut iter = block.into_iter().rev();
+    let last = iter.next().unwrap();
+    assert!(Rc::ptr_eq(&last, &resource));
+    assert_eq!(*iter.next().unwrap(), 6);
+    drop(iter);
+    assert_eq!(Rc::strong_count(&resource), 2);
+    drop(last);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn interleaved_into_iter_drops_middle() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [0, 2, 3, 5, 7] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    let mut iter = block.into_iter();
+    let front = iter.next().unwrap();
+    let back = iter.next_back().unwrap();
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    // Only the three middle elements remain in the iterator.
+    drop(iter);
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    drop(front);
+    drop(back);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn full_into_iter_consumed_from_both_ends() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let block = Block8::from([(); 8].map(|_| resource.clone()));
+    assert_eq!(Rc::strong_count(&resource), 9);
+
+    let mut iter = block.into_iter();
+    assert_eq!(iter.len(), 8);
+    drop(iter.next_back());
+    drop(iter.next());
+    drop(iter.next_back());
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    drop(iter);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn empty_into_iter() {
+    use std::rc::Rc;
+    let mut iter = Block8::<Rc<()>>::default().into_iter();
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+}
+
+#[test]
+fn cloned_into_iter_owns_remaining() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [0, 3, 4, 7] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+
+    let mut iter = block.into_iter();
+    drop(iter.next());
+    drop(iter.next_back());
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    // Only the two remaining elements are deep-cloned.
+    let copy = iter.clone();
+    assert_eq!(copy.len(), 2);
+    assert_eq!(Rc::strong_count(&resource), 5);
+    assert_eq!(format!("{copy:?}"), "Block8IntoIter { mask: 0b11000, len: 2 }");
+
+    drop(iter);
+    assert_eq!(Rc::strong_count(&resource), 3);
+    assert_eq!(copy.count(), 2);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn into_iter_nth_drops_skipped() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [1, 2, 5, 6] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+
+    let mut iter = block.into_iter();
+    let third = iter.nth(2).unwrap();
+    assert_eq!(Rc::strong_count(&resource), 3);
+    assert_eq!(iter.len(), 1);
+
+    assert!(iter.nth(5).is_none());
+    assert_eq!(Rc::strong_count(&resource), 2);
+    drop(third);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+/// Increments the shared counter when dropped. Forgetting it never leaks memory.
+struct DropCounter<'a>(&'a core::cell::Cell<usize>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn from_raw_parts_drops_masked_slots() {
+    use core::{cell::Cell, mem::MaybeUninit};
+    let drops = Cell::new(0);
+    let data = core::array::from_fn(|_| MaybeUninit::new(DropCounter(&drops)));
+
+    // SAFETY: Every slot has been initialized above.
+    let block = unsafe { Block8::from_raw_parts(data, 0b1010_0001) };
+    assert_eq!(block.len(), 3);
+    assert!(block.occupied_indices().eq([0, 5, 7]));
+
+    // The unmasked slots are simply forgotten.
+    drop(block);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn from_raw_parts_with_zero_mask() {
+    use core::{cell::Cell, mem::MaybeUninit};
+
+    // SAFETY: No slot is marked as occupied.
+    let block = unsafe { Block8::<String>::from_raw_parts(core::array::from_fn(|_| MaybeUninit::uninit()), 0) };
+    assert!(block.is_empty());
+    assert!(block.iter().next().is_none());
+    drop(block);
+
+    let drops = Cell::new(0);
+    let data = core::array::from_fn(|_| MaybeUninit::new(DropCounter(&drops)));
+    // SAFETY: No slot is marked as occupied.
+    let block = unsafe { Block8::from_raw_parts(data, 0) };
+    assert!(block.into_iter().next().is_none());
+    assert_eq!(drops.get(), 0);
+}
+
+#[test]
+fn raw_parts_round_trip() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [1, 4, 6] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+
+    let (data, mask) = block.into_raw_parts();
+    assert_eq!(mask, 0b0101_0010);
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    // SAFETY: The slot is occupied according to the mask, and it is removed from the mask below.
+    drop(unsafe { data[4].assume_init_read() });
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    // SAFETY: The remaining masked slots are still initialized.
+    let block = unsafe { Block8::from_raw_parts(data, mask & !(1 << 4)) };
+    assert!(block.occupied_indices().eq([1, 6]));
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn remove_many_returns_each_value_once() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for i in [0, 2, 5, 7] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+
+    let [first, vacant, duplicate, second] = block.remove_many([5, 1, 5, 0]);
+    assert!(vacant.is_none());
+    assert!(duplicate.is_none());
+    assert_eq!(Rc::strong_count(&resource), 5);
+    assert!(block.occupied_indices().eq([2, 7]));
+
+    drop((first.unwrap(), second.unwrap()));
+    assert_eq!(Rc::strong_count(&resource), 3);
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn insert_many_reports_displaced() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    assert!(block.insert(3, resource.clone()).is_none());
+
+    let [first, second, third] =
+        block.insert_many([(3, resource.clone()), (4, resource.clone()), (4, resource.clone())]);
+    assert!(first.is_some());
+    assert!(second.is_none());
+    assert!(third.is_some());
+    assert!(block.occupied_indices().eq([3, 4]));
+
+    drop((first, third));
+    assert_eq!(Rc::strong_count(&resource), 3);
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn try_insert_many_is_all_or_nothing() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    assert!(block.insert(6, resource.clone()).is_none());
+
+    // A single collision rejects the whole batch.
+    let entries = [(0, resource.clone()), (6, resource.clone()), (2, resource.clone())];
+    let entries = block.try_insert_many(entries).unwrap_err();
+    assert!(block.occupied_indices().eq([6]));
+    assert_eq!(Rc::strong_count(&resource), 5);
+    drop(entries);
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    // Out-of-bounds and repeated indices are rejected as well.
+    assert!(block.try_insert_many([(1, resource.clone()), (8, resource.clone())]).is_err());
+    assert!(block.try_insert_many([(1, resource.clone()), (1, resource.clone())]).is_err());
+    assert!(block.occupied_indices().eq([6]));
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    assert!(block.try_insert_many([(0, resource.clone()), (7, resource.clone())]).is_ok());
+    assert!(block.occupied_indices().eq([0, 6, 7]));
+    assert_eq!(Rc::strong_count(&resource), 4);
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn insert_with_panicking_closure() {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    assert!(block.insert_with(1, || resource.clone()).is_none());
+    assert!(block.insert(2, resource.clone()).is_none());
+
+    let displaced = block.insert_with(2, || resource.clone()).unwrap();
+    assert_eq!(Rc::strong_count(&resource), 4);
+    drop(displaced);
+
+    // The displaced value is dropped and the slot is left vacant.
+    let result = catch_unwind(AssertUnwindSafe(|| block.insert_with(2, || panic!("construction failed"))));
+    assert!(result.is_err());
+    assert!(block.is_vacant(2));
+    assert!(block.occupied_indices().eq([1]));
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    let result = catch_unwind(AssertUnwindSafe(|| block.insert_with(5, || panic!("construction failed"))));
+    assert!(result.is_err());
+    assert!(block.is_vacant(5));
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn replace_only_when_occupied() {
+    use std::rc::Rc;
+    let old = Rc::new(0);
+    let new = Rc::new(1);
+    let mut block = Block8::default();
+    assert!(block.insert(3, old.clone()).is_none());
+
+    // The value is handed back as is from a vacant slot.
+    let rejected = block.replace(4, new.clone()).unwrap_err();
+    assert!(Rc::ptr_eq(&rejected, &new));
+    assert_eq!(Rc::strong_count(&new), 2);
+    assert_eq!(block.occupied_mask(), 1 << 3);
+
+    let prev = block.replace(3, rejected).unwrap();
+    assert!(Rc::ptr_eq(&prev, &old));
+    assert!(Rc::ptr_eq(&block[3], &new));
+    assert_eq!(block.occupied_mask(), 1 << 3);
+    drop(prev);
+    assert_eq!(Rc::strong_count(&old), 1);
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&new), 1);
+}
+
+#[test]
+fn pop_first_drains_in_order() {
+    use option_block::Block16;
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+
+    let mut sparse = Block16::default();
+    for i in [9, 2, 15, 4] {
+        assert!(sparse.insert(i, resource.clone()).is_none());
+    }
+    for (expected, remaining) in [(2, 4), (4, 3), (9, 2), (15, 1)] {
+        let (idx, val) = sparse.pop_first().unwrap();
+        assert_eq!(idx, expected);
+        drop(val);
+        assert_eq!(Rc::strong_count(&resource), remaining);
+    }
+    assert!(sparse.pop_first().is_none());
+    assert!(sparse.is_empty());
+
+    let mut full: Block8<_> = (0..8).map(|i| (i, resource.clone())).collect();
+    assert_eq!(Rc::strong_count(&resource), 9);
+    for expected in 0..8 {
+        assert_eq!(full.pop_first().map(|(idx, _)| idx), Some(expected));
+        assert_eq!(Rc::strong_count(&resource), 8 - expected);
+    }
+    assert!(full.pop_first().is_none());
+}
+
+#[test]
+fn interleaved_pops_never_repeat() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block: Block8<_> = [1, 3, 4, 6, 7].into_iter().map(|i| (i, resource.clone())).collect();
+    assert_eq!(block.highest_occupied_index(), Some(7));
+
+    let mut popped = 0u8;
+    for round in 0.. {
+        let entry = if round % 2 == 0 { block.pop_first() } else { block.pop_last() };
+        let Some((idx, val)) = entry else { break };
+        assert_eq!(popped & 1 << idx, 0);
+        popped |= 1 << idx;
+        drop(val);
+        assert_eq!(Rc::strong_count(&resource), 1 + block.len() as usize);
+    }
+
+    assert_eq!(popped, 0b1101_1010);
+    assert!(block.pop_last().is_none());
+    assert_eq!(block.highest_occupied_index(), None);
+}
+
+#[test]
+fn lazy_vacancy_insertion() {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    assert_eq!(block.insert_at_first_vacancy_with(|| resource.clone()), Some(0));
+    assert_eq!(block.insert_at_last_vacancy_with(|| resource.clone()), Some(7));
+    assert_eq!(block.insert_at_last_vacancy_with(|| resource.clone()), Some(6));
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    // A panicking constructor leaves the slot vacant.
+    let result =
+        catch_unwind(AssertUnwindSafe(|| block.insert_at_first_vacancy_with(|| panic!("construction failed"))));
+    assert!(result.is_err());
+    let result = catch_unwind(AssertUnwindSafe(|| block.insert_at_last_vacancy_with(|| panic!("construction failed"))));
+    assert!(result.is_err());
+    assert!(block.occupied_indices().eq([0, 6, 7]));
+
+    while block.insert_at_first_vacancy_with(|| resource.clone()).is_some() {}
+    assert_eq!(Rc::strong_count(&resource), 9);
+
+    // The closure is never invoked for a full block.
+    assert_eq!(block.insert_at_first_vacancy_with(|| unreachable!()), None);
+    assert_eq!(block.insert_at_last_vacancy_with(|| unreachable!()), None);
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn compact_sparse_blocks() {
+    use option_block::Block64;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let indices = [3, 4, 17, 40, 63];
+    let mut block = Block64::default();
+    for (order, idx) in indices.into_iter().enumerate() {
+        assert!(block.insert(idx, (order, resource.clone())).is_none());
+    }
+
+    let mut moves = Vec::new();
+    block.compact_with(|old, new| moves.push((old, new)));
+    assert_eq!(moves, [(3, 0), (4, 1), (17, 2), (40, 3), (63, 4)]);
+    assert_eq!(block.occupied_mask(), 0b1_1111);
+    assert!(block.iter().map(|(order, _)| *order).eq(0..5));
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    // An already dense prefix is left untouched.
+    block.compact_with(|_, _| unreachable!());
+    assert_eq!(block.occupied_mask(), 0b1_1111);
+
+    block.remove(1);
+    block.remove(3);
+    block.compact();
+    assert_eq!(block.occupied_mask(), 0b111);
+    assert!(block.iter().map(|(order, _)| *order).eq([0, 2, 4]));
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn compact_with_panicking_callback() {
+    use option_block::Block64;
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let resource = Rc::new(0);
+    let mut block = Block64::default();
+    for idx in [10, 20, 30] {
+        assert!(block.insert(idx, resource.clone()).is_none());
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| block.compact_with(|old, _| assert_ne!(old, 20))));
+    assert!(result.is_err());
+    assert!(block.occupied_indices().eq([0, 1, 30]));
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn rotations_are_drop_neutral() {
+    use option_block::Block32;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let mut block = Block32::default();
+    for idx in [0, 1, 30] {
+        assert!(block.insert(idx, (idx, resource.clone())).is_none());
+    }
+
+    let entries = |block: &Block32<(usize, Rc<i32>)>| -> Vec<_> {
+        block.iter_entries().map(|(idx, (orig, _))| (idx, *orig)).collect()
+    };
+
+    block.rotate_left(0);
+    block.rotate_right(32);
+    assert_eq!(entries(&block), [(0, 0), (1, 1), (30, 30)]);
+
+    block.rotate_left(1);
+    assert_eq!(entries(&block), [(0, 1), (29, 30), (31, 0)]);
+
+    block.rotate_right(1 + 32 + 3);
+    assert_eq!(entries(&block), [(1, 30), (3, 0), (4, 1)]);
+
+    block.rotate_left(32 + 3);
+    assert_eq!(entries(&block), [(0, 0), (1, 1), (30, 30)]);
+
+    block.rotate_right(32);
+    assert_eq!(Rc::strong_count(&resource), 4);
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn shifts_drop_what_falls_off() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for idx in [0, 2, 5, 7] {
+        assert!(block.insert(idx, (idx, resource.clone())).is_none());
+    }
+
+    let entries = |block: &Block8<(usize, Rc<i32>)>| -> Vec<_> {
+        block.iter_entries().map(|(idx, (orig, _))| (idx, *orig)).collect()
+    };
+
+    block.shift_left(0);
+    assert_eq!(entries(&block), [(0, 0), (2, 2), (5, 5), (7, 7)]);
+
+    block.shift_left(2);
+    assert_eq!(entries(&block), [(0, 2), (3, 5), (5, 7)]);
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    block.shift_right(3);
+    assert_eq!(entries(&block), [(3, 2), (6, 5)]);
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    block.shift_right(8);
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 1);
+
+    assert!(block.insert(4, (4, resource.clone())).is_none());
+    block.shift_left(u32::MAX);
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn shift_insert_preserves_order() {
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    for idx in [1, 2, 4] {
+        assert!(block.insert(idx, (idx, resource.clone())).is_none());
+    }
+
+    let entries = |block: &Block8<(usize, Rc<i32>)>| -> Vec<_> {
+        block.iter_entries().map(|(idx, (orig, _))| (idx, *orig)).collect()
+    };
+
+    // Shifting across the gap at index 3
+    assert!(block.shift_insert(2, (10, resource.clone())).is_none());
+    assert_eq!(entries(&block), [(1, 1), (2, 10), (3, 2), (5, 4)]);
+
+    assert!(block.shift_insert(7, (70, resource.clone())).is_none());
+    let (top, _) = block.shift_insert(0, (0, resource.clone())).unwrap();
+    assert_eq!(top, 70);
+    assert_eq!(entries(&block), [(0, 0), (2, 1), (3, 10), (4, 2), (6, 4)]);
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    // Shifting a full block gives back the top element.
+    let mut full: Block8<_> = (0..8).map(|idx| (idx, (idx, resource.clone()))).collect();
+    assert_eq!(Rc::strong_count(&resource), 14);
+    let (top, _) = full.shift_insert(3, (30, resource.clone())).unwrap();
+    assert_eq!(top, 7);
+    assert_eq!(Rc::strong_count(&resource), 14);
+    assert!(full.iter().map(|(orig, _)| *orig).eq([0, 1, 2, 30, 3, 4, 5, 6]));
+
+    drop((block, full));
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn truncate_drops_upper_entries() {
+    use option_block::Block128;
+    use std::rc::Rc;
+    let resource = Rc::new(0);
+    let mut block = Block128::default();
+    for idx in [0, 5, 63, 64, 127] {
+        assert!(block.insert(idx, resource.clone()).is_none());
+    }
+
+    block.truncate(128);
+    block.truncate(usize::MAX);
+    assert_eq!(Rc::strong_count(&resource), 6);
+
+    block.truncate(64);
+    assert!(block.occupied_indices().eq([0, 5, 63]));
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    block.truncate(6);
+    assert!(block.occupied_indices().eq([0, 5]));
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    block.truncate(0);
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn map_into_new_block() {
+    use option_block::Block32;
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let resource = Rc::new(0);
+    let block: Block32<_> = [3, 8, 31].into_iter().map(|idx| (idx, (idx, resource.clone()))).collect();
+    let mapped = block.map(|(idx, rc)| (idx * 2, rc));
+    assert!(mapped.iter_entries().map(|(idx, (doubled, _))| (idx, *doubled)).eq([(3, 6), (8, 16), (31, 62)]));
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    // Both the mapped head and the unmapped tail are dropped exactly once.
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        mapped.map(|(doubled, rc)| {
+            assert_ne!(doubled, 16);
+            rc
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn try_map_drops_on_failure() {
+    use option_block::Block16;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let block: Block16<_> = [1, 4, 9, 12].into_iter().map(|idx| (idx, resource.clone())).collect();
+    let mapped = block.try_map(|idx, rc| Ok::<_, ()>((idx, rc))).unwrap();
+    assert_eq!(mapped.occupied_mask(), 0b0001_0010_0001_0010);
+    assert_eq!(Rc::strong_count(&resource), 5);
+
+    // The converted head and the unconverted tail are dropped along with the error.
+    let other = resource.clone();
+    let result = mapped.try_map(|idx, (_, rc)| if idx == 9 { Err(other.clone()) } else { Ok(rc) });
+    let (idx, err) = result.unwrap_err();
+    assert_eq!(idx, 9);
+    assert_eq!(Rc::strong_count(&resource), 3);
+    drop((err, other));
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn filter_map_into_new_block() {
+    use std::rc::Rc;
+
+    let kept = Rc::new(0);
+    let dropped = Rc::new(0);
+    let mut block = Block8::default();
+    for idx in [0, 2, 5] {
+        assert!(block.insert(idx, (true, kept.clone())).is_none());
+    }
+    for idx in [1, 7] {
+        assert!(block.insert(idx, (false, dropped.clone())).is_none());
+    }
+
+    let filtered = block.filter_map(|idx, (keep, rc)| keep.then_some((idx, rc)));
+    assert!(filtered.iter_entries().all(|(idx, (orig, _))| idx == *orig));
+    assert!(filtered.occupied_indices().eq([0, 2, 5]));
+    assert_eq!(Rc::strong_count(&kept), 4);
+    assert_eq!(Rc::strong_count(&dropped), 1);
+
+    let emptied = filtered.filter_map(|_, _| None::<()>);
+    assert!(emptied.is_empty());
+    assert_eq!(Rc::strong_count(&kept), 1);
+}
+
+#[test]
+fn zip_pairs_common_indices() {
+    use option_block::Block64;
+    use std::rc::Rc;
+
+    let left = Rc::new(0);
+    let right = Rc::new(0);
+    let build = |indices: &[usize], rc: &Rc<i32>| -> Block64<(usize, Rc<i32>)> {
+        indices.iter().map(|&idx| (idx, (idx, rc.clone()))).collect()
+    };
+
+    // Disjoint masks
+    let zipped = build(&[0, 10], &left).zip(build(&[5, 63], &right));
+    assert!(zipped.is_empty());
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (1, 1));
+
+    // Overlapping masks
+    let a = build(&[1, 2, 40], &left);
+    let b = build(&[2, 40, 41], &right);
+    assert!(a.zip_ref(&b).map(|(idx, (x, _), (y, _))| (idx, *x, *y)).eq([(2, 2, 2), (40, 40, 40)]));
+    let zipped = a.zip(b);
+    assert!(zipped.occupied_indices().eq([2, 40]));
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (3, 3));
+    drop(zipped);
+
+    // Identical masks
+    let zipped = build(&[7, 8, 9], &left).zip(build(&[7, 8, 9], &right));
+    assert_eq!(zipped.len(), 3);
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (4, 4));
+    drop(zipped);
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (1, 1));
+}
+
+#[test]
+fn merge_with_conflict_resolver() {
+    use option_block::Block16;
+    use std::rc::Rc;
+
+    let left = Rc::new(0);
+    let right = Rc::new(0);
+    let build = |indices: &[usize], rc: &Rc<i32>| -> Block16<Vec<Rc<i32>>> {
+        indices.iter().map(|&idx| (idx, vec![rc.clone()])).collect()
+    };
+
+    // Keep the current value
+    let mut block = build(&[0, 1], &left);
+    block.merge(build(&[1, 2], &right), |_, current, _| current);
+    assert!(block.iter().map(|rcs| Rc::ptr_eq(&rcs[0], &left)).eq([true, true, false]));
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (3, 2));
+    drop(block);
+
+    // Keep the incoming value
+    let mut block = build(&[0, 1], &left);
+    block.merge(build(&[1, 2], &right), |_, _, incoming| incoming);
+    assert!(block.iter().map(|rcs| Rc::ptr_eq(&rcs[0], &left)).eq([true, false, false]));
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (2, 3));
+    drop(block);
+
+    // Combine both values
+    let mut block = build(&[0, 1], &left);
+    block.merge(build(&[1, 2], &right), |idx, mut current, incoming| {
+        assert_eq!(idx, 1);
+        current.extend(incoming);
+        current
+    });
+    assert!(block.iter().map(Vec::len).eq([1, 2, 1]));
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (3, 3));
+    drop(block);
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (1, 1));
+}
+
+#[test]
+fn intersect_with_other_blocks() {
+    use option_block::Block32;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let build =
+        |indices: &[usize]| -> Block32<Rc<i32>> { indices.iter().map(|&idx| (idx, resource.clone())).collect() };
+
+    // Identical masks are a no-op.
+    let mut block = build(&[1, 4, 30]);
+    block.intersect_with(&Block32::<()>::from_iter([(1, ()), (4, ()), (30, ())]));
+    assert!(block.occupied_indices().eq([1, 4, 30]));
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    // Partially overlapping masks drop exactly the removed entries.
+    block.intersect_with_mask(1 << 4 | 1 << 30 | 1 << 31);
+    assert!(block.occupied_indices().eq([4, 30]));
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    // Disjoint masks empty the block.
+    block.intersect_with(&Block32::<char>::from_iter([(0, 'a'), (31, 'b')]));
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn subtract_tombstones() {
+    use option_block::Block32;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let build =
+        |indices: &[usize]| -> Block32<Rc<i32>> { indices.iter().map(|&idx| (idx, resource.clone())).collect() };
+    let tombstones = |indices: &[usize]| -> Block32<()> { indices.iter().map(|&idx| (idx, ())).collect() };
+
+    // Overlapping tombstones
+    let mut block = build(&[0, 3, 9, 31]);
+    block.subtract(&tombstones(&[3, 4, 31]));
+    assert!(block.occupied_indices().eq([0, 9]));
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    // Tombstones as a subset of the occupancy
+    block.subtract_mask(1 << 9);
+    assert!(block.occupied_indices().eq([0]));
+    assert_eq!(Rc::strong_count(&resource), 2);
+
+    // Tombstones as a superset of the occupancy
+    block.subtract(&tombstones(&[0, 1, 2]));
+    assert!(block.is_empty());
+    assert_eq!(Rc::strong_count(&resource), 1);
+
+    let mut block = build(&[5]);
+    block.subtract(&tombstones(&[]));
+    assert_eq!(Rc::strong_count(&resource), 2);
+}
+
+#[test]
+fn symmetric_difference_of_snapshots() {
+    use std::rc::Rc;
+
+    let left = Rc::new(0);
+    let right = Rc::new(0);
+    let build =
+        |indices: &[usize], rc: &Rc<i32>| -> Block8<Rc<i32>> { indices.iter().map(|&idx| (idx, rc.clone())).collect() };
+
+    let a = build(&[0, 2, 4, 6], &left);
+    let b = build(&[1, 2, 6, 7], &right);
+    let mask = a.occupied_mask() ^ b.occupied_mask();
+
+    let diff = a.symmetric_difference(b);
+    assert_eq!(diff.occupied_mask(), mask);
+    assert!(diff.iter().map(|rc| Rc::ptr_eq(rc, &left)).eq([true, false, true, false]));
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (3, 3));
+
+    let same = diff.symmetric_difference(build(&[0, 1, 4, 7], &right));
+    assert!(same.is_empty());
+    assert_eq!((Rc::strong_count(&left), Rc::strong_count(&right)), (1, 1));
+}
+
+#[test]
+fn fill_every_slot() {
+    use option_block::Block128;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let displaced = Rc::new(0);
+    let mut block = Block128::default();
+    for idx in [0, 64, 127] {
+        assert!(block.insert(idx, displaced.clone()).is_none());
+    }
+
+    block.fill(resource.clone());
+    assert!(block.is_full());
+    assert_eq!(Rc::strong_count(&resource), 128 + 1);
+    assert_eq!(Rc::strong_count(&displaced), 1);
+
+    block.fill(displaced.clone());
+    assert_eq!(Rc::strong_count(&resource), 1);
+    assert_eq!(Rc::strong_count(&displaced), 128 + 1);
+}
+
+#[test]
+fn fill_with_panicking_closure() {
+    use option_block::Block16;
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let resource = Rc::new(0);
+    let mut block = Block16::default();
+    block.fill_with(|idx| (idx, resource.clone()));
+    assert!(block.iter_entries().all(|(idx, (orig, _))| idx == *orig));
+    assert_eq!(Rc::strong_count(&resource), 17);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        block.fill_with(|idx| {
+            assert_ne!(idx, 9);
+            (idx, resource.clone())
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(block.occupied_mask(), 0b1_1111_1111);
+    assert_eq!(Rc::strong_count(&resource), 10);
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn fill_vacancies_leaves_occupied_untouched() {
+    use option_block::Block32;
+    use std::rc::Rc;
+
+    let existing: Vec<_> = (0..3).map(Rc::new).collect();
+    let padding = Rc::new(0);
+    let mut block = Block32::default();
+    for (rc, idx) in existing.iter().zip([0, 7, 31]) {
+        assert!(block.insert(idx, rc.clone()).is_none());
+    }
+
+    block.fill_vacancies(padding.clone());
+    assert!(block.is_full());
+    assert_eq!(Rc::strong_count(&padding), 29 + 1);
+    assert!(existing.iter().zip([0, 7, 31]).all(|(rc, idx)| Rc::ptr_eq(rc, &block[idx])));
+
+    // Nothing happens to a full block (and the given value is dropped).
+    block.fill_vacancies(padding.clone());
+    assert_eq!(Rc::strong_count(&padding), 29 + 1);
+
+    let mut calls = 0;
+    let mut sparse: Block32<_> = existing.iter().cloned().zip([1, 2, 3]).map(|(rc, idx)| (idx, rc)).collect();
+    assert_eq!(sparse.vacancies(), 29);
+    sparse.fill_vacancies_with(|idx| {
+        calls += 1;
+        assert!(![1, 2, 3].contains(&idx));
+        padding.clone()
+    });
+    assert_eq!(calls, 29);
+    assert!(existing.iter().zip([1, 2, 3]).all(|(rc, idx)| Rc::ptr_eq(rc, &sparse[idx])));
+    sparse.fill_vacancies_with(|_| unreachable!());
+
+    drop((block, sparse));
+    assert_eq!(Rc::strong_count(&padding), 1);
+    assert!(existing.iter().all(|rc| Rc::strong_count(rc) == 1));
+}
+
+#[test]
+fn from_fn_with_panicking_closure() {
+    use option_block::Block16;
+    use std::{panic::catch_unwind, rc::Rc};
+
+    let resource = Rc::new(0);
+    let result = catch_unwind(|| {
+        Block16::from_fn(|idx| {
+            assert_ne!(idx, 10);
+            (idx % 2 == 0).then(|| resource.clone())
+        })
+    });
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn from_mask_and_fn_with_panicking_closure() {
+    use option_block::Block16;
+    use std::{panic::catch_unwind, rc::Rc};
+
+    let resource = Rc::new(0);
+    let result = catch_unwind(|| {
+        Block16::from_mask_and_fn(0b0110_1011, |idx| {
+            assert_ne!(idx, 5);
+            resource.clone()
+        })
+    });
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn chained_builders() {
+    use std::rc::Rc;
+
+    let first = Rc::new("first");
+    let second = Rc::new("second");
+    let block = Block8::default()
+        .with(0, first.clone())
+        .with(5, first.clone())
+        .with(5, second.clone())
+        .with(7, second.clone())
+        .without(0)
+        .without(3);
+
+    assert!(block.occupied_indices().eq([5, 7]));
+    assert!(block.iter().all(|rc| Rc::ptr_eq(rc, &second)));
+    assert_eq!(Rc::strong_count(&first), 1);
+    assert_eq!(Rc::strong_count(&second), 3);
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&second), 1);
+}
+
+#[test]
+fn from_options_moves_values() {
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let vals: [Option<Rc<i32>>; 8] = core::array::from_fn(|idx| (idx % 3 == 0).then(|| resource.clone()));
+    let block = Block8::from_options(vals);
+    assert!(block.occupied_indices().eq([0, 3, 6]));
+    assert_eq!(Rc::strong_count(&resource), 4);
+
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn exact_into_iter_owns_remaining() {
+    use option_block::Block;
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let mut block = Block::<_, 11>::default();
+    for i in [0, 3, 4, 10] {
+        assert!(block.insert(i, resource.clone()).is_none());
+    }
+
+    let mut iter = block.into_iter();
+    drop(iter.next());
+    drop(iter.next_back());
+    assert_eq!(Rc::strong_count(&resource), 3);
+
+    // Only the two remaining elements are deep-cloned.
+    let copy = iter.clone();
+    assert_eq!(Rc::strong_count(&resource), 5);
+    assert_eq!(format!("{copy:?}"), "BlockIntoIter { mask: 0b11000, len: 2 }");
+
+    drop(iter);
+    assert_eq!(Rc::strong_count(&resource), 3);
+    drop(copy);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
+
+#[test]
+fn checked_insert_hands_back_value() {
+    use std::rc::Rc;
+
+    let resource = Rc::new(0);
+    let mut block = Block8::default();
+    assert_eq!(block.checked_insert(3, resource.clone()), Ok(None));
+
+    let (err, val) = block.checked_insert(8, resource.clone()).unwrap_err();
+    assert_eq!(err.index, 8);
+    assert!(Rc::ptr_eq(&val, &resource));
+    assert_eq!(Rc::strong_count(&resource), 3);
+    assert!(block.occupied_indices().eq([3]));
+
+    drop(val);
+    drop(block);
+    assert_eq!(Rc::strong_count(&resource), 1);
+}
@@ -0,0 +1,95 @@
+//! This test module checks that the mask-driven iterators visit exactly the
+//! same slots (in the same order) as a naive linear probe over every index.
+
+mod common;
+
+use common::XorShift;
+use option_block::{Block, Block128, Block16, Block32, Block64, Block8};
+
+type Exact1<T> = Block<T, 1>;
+type Exact11<T> = Block<T, 11>;
+type Exact64<T> = Block<T, 64>;
+type Exact128<T> = Block<T, 128>;
+
+macro_rules! check_linear_equivalence {
+    ($rng:ident $($block:ident)*) => {$(
+        for _ in 0..256 {
+            let capacity = $block::<u64>::CAPACITY as usize;
+            let density = $rng.next() % 4;
+            let mut block = $block::<u64>::default();
+            for index in 0..capacity {
+                let val = $rng.next();
+                if val % 4 < density {
+                    assert!(block.insert(index, val).is_none());
+                }
+            }
+
+            let linear: Vec<_> = (0..capacity).filter_map(|index| block.get(index).map(|val| (index, *val))).collect();
+            let linear = linear.iter().copied();
+            assert!(block.iter().copied().eq(linear.clone().map(|(_, val)| val)));
+            assert!(block.iter_entries().map(|(index, val)| (index, *val)).eq(linear.clone()));
+            assert!(block.iter_entries_mut().map(|(index, val)| (index, *val)).eq(linear.clone()));
+            assert_eq!(block.iter().count(), linear.clone().count());
+            assert_eq!(block.iter().len(), linear.clone().count());
+
+            let skip = ($rng.next() % 8) as usize;
+            assert_eq!(block.iter_entries().nth(skip).map(|(index, val)| (index, *val)), linear.clone().nth(skip));
+            assert_eq!(block.iter().last(), linear.last().map(|(index, _)| block.get(index).unwrap()));
+        }
+    )*};
+}
+
+#[test]
+fn random_masks_match_linear_probe() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    check_linear_equivalence!(rng Block8 Block16 Block32 Block64 Block128);
+    check_linear_equivalence!(rng Exact1 Exact11 Exact64 Exact128);
+}
+
+/// Hides every specialized method so that only the default implementations remain.
+fn naive<I: Iterator>(mut iter: I) -> impl Iterator<Item = I::Item> {
+    core::iter::from_fn(move || iter.next())
+}
+
+macro_rules! check_specialized_methods {
+    ($rng:ident $($block:ident)*) => {$(
+        for _ in 0..256 {
+            let capacity = $block::<u64>::CAPACITY as usize;
+            let mut block: $block<u64> = (0..capacity)
+                .filter_map(|index| Some($rng.next()).filter(|val| val % 3 == 0).map(|val| (index, val)))
+                .collect();
+            let n = ($rng.next() % (capacity as u64 / 2 + 2)) as usize;
+
+            assert_eq!(block.iter().count(), naive(block.iter()).count());
+            assert_eq!(block.iter().last(), naive(block.iter()).last());
+            let (mut fast, mut slow) = (block.iter(), naive(block.iter()));
+            assert_eq!(fast.nth(n), slow.nth(n));
+            assert!(fast.eq(slow));
+
+            assert_eq!(block.iter_entries().count(), naive(block.iter_entries()).count());
+            assert_eq!(block.iter_entries().last(), naive(block.iter_entries()).last());
+            assert_eq!(block.iter_entries().nth(n), naive(block.iter_entries()).nth(n));
+
+            let expected = naive(block.iter_entries()).last().map(|(index, val)| (index, *val));
+            assert_eq!(block.iter_entries_mut().last().map(|(index, val)| (index, *val)), expected);
+            let expected = naive(block.iter_entries()).nth(n).map(|(index, val)| (index, *val));
+            assert_eq!(block.iter_entries_mut().nth(n).map(|(index, val)| (index, *val)), expected);
+            assert_eq!(block.iter_entries_mut().count(), block.len() as usize);
+
+            assert_eq!(block.clone().into_iter().count(), naive(block.clone().into_iter()).count());
+            assert_eq!(block.clone().into_iter().last(), naive(block.clone().into_iter()).last());
+            let (mut fast, mut slow) = (block.clone().into_iter(), naive(block.clone().into_iter()));
+            assert_eq!(fast.nth(n), slow.nth(n));
+            assert!(fast.eq(slow));
+            assert_eq!(block.clone().into_entries().last(), naive(block.clone().into_entries()).last());
+            assert_eq!(block.clone().into_entries().nth(n), naive(block.clone().into_entries()).nth(n));
+        }
+    )*};
+}
+
+#[test]
+fn specialized_methods_match_defaults() {
+    let mut rng = XorShift(0x853c_49e6_748f_ea9b);
+    check_specialized_methods!(rng Block8 Block16 Block32 Block64 Block128);
+    check_specialized_methods!(rng Exact1 Exact11 Exact64 Exact128);
+}
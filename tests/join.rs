@@ -0,0 +1,39 @@
+//! This test module checks that `join_blocks!` visits exactly the indices
+//! occupied in all of its operands, with any mix of shared and exclusive borrows.
+
+use option_block::{join_blocks, Block64, Block8};
+
+#[test]
+fn three_way_join() {
+    let mut a: Block64<u32> = [(0, 0), (3, 30), (40, 400), (63, 630)].into_iter().collect();
+    let b: Block64<u32> = [(3, 3), (40, 4), (50, 5), (63, 6)].into_iter().collect();
+    let mut c: Block64<u32> = [(1, 0), (3, 0), (63, 0)].into_iter().collect();
+
+    let mut visited = Vec::new();
+    for (idx, x, y, z) in join_blocks!(&mut a, &b, &mut c) {
+        visited.push(idx);
+        *x += y;
+        *z = *x;
+    }
+
+    assert_eq!(visited, [3, 63]);
+    assert!(a.iter_entries().map(|(idx, val)| (idx, *val)).eq([(0, 0), (3, 33), (40, 400), (63, 636)]));
+    assert!(c.iter_entries().map(|(idx, val)| (idx, *val)).eq([(1, 0), (3, 33), (63, 636)]));
+
+    // Shared borrows may be repeated.
+    assert!(join_blocks!(&a, &a, &b).map(|(idx, ..)| idx).eq([3, 40, 63]));
+    assert!(join_blocks!(&b).map(|(idx, val)| (idx, *val)).eq(b.iter_entries().map(|(idx, val)| (idx, *val))));
+}
+
+#[test]
+fn joins_across_widths() {
+    let wide: Block64<char> = [(2, 'a'), (7, 'b'), (20, 'c')].into_iter().collect();
+    let mut narrow: Block8<char> = [(2, 'x'), (7, 'y')].into_iter().collect();
+    for (_, src, dst) in join_blocks!(&wide, &mut narrow) {
+        *dst = *src;
+    }
+    assert!(narrow.iter().eq(&['a', 'b']));
+
+    let empty = Block8::<()>::default();
+    assert!(join_blocks!(&wide, &empty).next().is_none());
+}
@@ -0,0 +1,47 @@
+//! This test module checks that the [`KeyedBlock`](option_block::keyed::KeyedBlock)
+//! behaves exactly like a [`BTreeMap`] bounded to the same capacity.
+
+mod common;
+
+use common::XorShift;
+use option_block::{keyed::KeyedBlock, Block32};
+use std::collections::BTreeMap;
+
+#[test]
+fn randomized_parity_with_btree_map() {
+    let mut rng = XorShift(0xdead_beef_cafe_f00d);
+    let mut keyed = KeyedBlock::<u16, u64, Block32<u16>>::default();
+    let mut model = BTreeMap::new();
+
+    for _ in 0..8192 {
+        let key = (rng.next() % 64) as u16 * 1000;
+        match rng.next() % 3 {
+            0 | 1 => {
+                let val = rng.next();
+                if model.len() < 32 || model.contains_key(&key) {
+                    assert_eq!(keyed.insert(key, val), Ok(model.insert(key, val)));
+                } else {
+                    assert_eq!(keyed.insert(key, val), Err((key, val)));
+                }
+            }
+            _ => assert_eq!(keyed.remove(&key), model.remove(&key)),
+        }
+
+        assert_eq!(keyed.len() as usize, model.len());
+        assert_eq!(keyed.get(&key), model.get(&key));
+        assert!(keyed.iter().eq(model.iter()));
+    }
+}
+
+#[test]
+fn mutation_through_references() {
+    let mut keyed = KeyedBlock::<u16, String, Block32<u16>>::default();
+    assert!(keyed.is_empty());
+    assert_eq!(keyed.insert(7, String::from("Hello")), Ok(None));
+    keyed.get_mut(&7).unwrap().push_str(" World");
+    assert!(keyed.get_mut(&8).is_none());
+    assert_eq!(keyed.get(&7).map(String::as_str), Some("Hello World"));
+    assert!(keyed.contains_key(&7));
+    assert_eq!(keyed.remove(&7).as_deref(), Some("Hello World"));
+    assert!(keyed.is_empty());
+}
@@ -0,0 +1,35 @@
+//! This test module checks that the raw occupancy mask stays consistent with
+//! the per-index queries across random sequences of insertions and removals.
+
+mod common;
+
+use common::XorShift;
+use option_block::{Block128, Block16, Block32, Block64, Block8};
+
+macro_rules! check_mask_consistency {
+    ($rng:ident $($block:ident)*) => {$(
+        let capacity = $block::<u64>::CAPACITY as usize;
+        let mut block = $block::<u64>::default();
+        assert_eq!(block.occupied_mask(), 0);
+        for _ in 0..1024 {
+            let index = ($rng.next() % capacity as u64) as usize;
+            if $rng.next() % 2 == 0 {
+                block.insert(index, $rng.next());
+            } else {
+                block.remove(index);
+            }
+
+            let mask = block.occupied_mask();
+            assert_eq!(mask.count_ones(), block.len());
+            for index in 0..capacity {
+                assert_eq!(mask >> index & 1 == 0, block.is_vacant(index));
+            }
+        }
+    )*};
+}
+
+#[test]
+fn mask_matches_vacancy() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    check_mask_consistency!(rng Block8 Block16 Block32 Block64 Block128);
+}
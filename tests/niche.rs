@@ -0,0 +1,49 @@
+//! This test module checks that the [`NicheBlock`](option_block::niche::NicheBlock)
+//! behaves exactly like its mask-based counterpart.
+
+mod common;
+
+use common::XorShift;
+use core::{
+    mem::size_of,
+    num::{NonZeroU32, NonZeroU8},
+};
+use option_block::{niche::NicheBlock, Block64};
+
+#[test]
+fn size_claims() {
+    assert_eq!(size_of::<NicheBlock<NonZeroU32, 64>>(), 64 * 4);
+    assert_eq!(size_of::<NicheBlock<NonZeroU8, 11>>(), 11);
+    assert!(size_of::<NicheBlock<NonZeroU32, 64>>() < size_of::<Block64<NonZeroU32>>());
+}
+
+#[test]
+fn randomized_parity_with_block64() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+    let mut niche = NicheBlock::<NonZeroU32, 64>::default();
+    let mut block = Block64::<NonZeroU32>::default();
+
+    for _ in 0..4096 {
+        let index = (rng.next() % 64) as usize;
+        match rng.next() % 4 {
+            0 | 1 => {
+                let val = NonZeroU32::new(rng.next() as u32 | 1).unwrap();
+                assert_eq!(niche.insert(index, val), block.insert(index, val));
+            }
+            2 => assert_eq!(niche.remove(index), block.remove(index)),
+            _ => {
+                if let (Some(a), Some(b)) = (niche.get_mut(index), block.get_mut(index)) {
+                    *a = a.saturating_add(1);
+                    *b = b.saturating_add(1);
+                }
+            }
+        }
+
+        assert_eq!(niche.is_vacant(index), block.is_vacant(index));
+        assert_eq!(niche.get(index), block.get(index));
+        assert_eq!(niche.len(), block.len() as usize);
+        assert_eq!(niche.is_empty(), block.is_empty());
+    }
+
+    assert!(niche.iter().eq(block.iter()));
+}
@@ -0,0 +1,68 @@
+//! This test module checks the raw byte representation of blocks of plain-old-data.
+#![cfg(feature = "bytemuck")]
+
+mod common;
+
+use common::XorShift;
+use option_block::{pod::ByteLengthError, Block128, Block16, Block32, Block64, Block8};
+
+macro_rules! check_round_trips {
+    ($rng:ident $($block:ident)*) => {$(
+        for _ in 0..64 {
+            let bytes: $block<u8> = (0..$block::<u8>::CAPACITY as usize)
+                .filter_map(|idx| Some($rng.next()).filter(|val| val % 3 == 0).map(|val| (idx, val as u8)))
+                .collect();
+            let words: $block<u32> = bytes.iter_entries().map(|(idx, &val)| (idx, u32::from(val) << 20)).collect();
+
+            // Offset the buffer by one byte to exercise unaligned reads.
+            let mut buf = [0xaa; 1 + 16 + 128 * 4];
+            let len = $block::<u32>::byte_len();
+            words.write_bytes(&mut buf[1..1 + len]).unwrap();
+            assert_eq!($block::<u32>::from_bytes(&buf[1..1 + len]).unwrap(), words);
+
+            let len = $block::<u8>::byte_len();
+            bytes.write_bytes(&mut buf[1..1 + len]).unwrap();
+            assert_eq!($block::<u8>::from_bytes(&buf[1..1 + len]).unwrap(), bytes);
+        }
+    )*};
+}
+
+#[test]
+fn round_trips_over_all_sizes() {
+    let mut rng = XorShift(0xdead_beef_cafe_f00d);
+    check_round_trips!(rng Block8 Block16 Block32 Block64 Block128);
+}
+
+#[test]
+fn layout_and_length_validation() {
+    assert_eq!(Block8::<u8>::byte_len(), 1 + 8);
+    assert_eq!(Block32::<u16>::byte_len(), 4 + 64);
+    assert_eq!(Block128::<u32>::byte_len(), 16 + 512);
+
+    let block: Block16<u16> = [(0, 0x0102), (15, 0xffff)].into_iter().collect();
+    let mut buf = [0xaa; 2 + 32];
+    block.write_bytes(&mut buf).unwrap();
+    assert_eq!(buf[..6], [0x01, 0x80, 0x02, 0x01, 0x00, 0x00]);
+    assert!(buf[6..32].iter().all(|&byte| byte == 0));
+    assert_eq!(buf[32..], [0xff, 0xff]);
+
+    let err = block.write_bytes(&mut buf[1..]).unwrap_err();
+    assert_eq!(err, ByteLengthError { expected: 34, found: 33 });
+    assert!(Block16::<u16>::from_bytes(&[0; 35]).is_err());
+
+    // Garbage in the bytes of vacant slots never shows up.
+    let mut buf = [0xff; 1 + 8 * 4];
+    buf[0] = 0b0000_0100;
+    let restored = Block8::<u32>::from_bytes(&buf).unwrap();
+    assert!(restored.iter_entries().eq([(2, &u32::MAX)]));
+}
+
+#[test]
+fn zeroed_blocks_are_empty() {
+    let block = Block64::<u64>::zeroed();
+    assert!(block.is_empty());
+    let mut buf = [0xaa; 8 + 64 * 8];
+    block.write_bytes(&mut buf).unwrap();
+    assert!(buf.iter().all(|&byte| byte == 0));
+    assert!(Block8::<()>::from_bytes(&[0xff]).unwrap().is_full());
+}
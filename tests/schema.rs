@@ -0,0 +1,43 @@
+//! This test module checks that the generated JSON Schema matches the dense `serde` encoding.
+#![cfg(feature = "schemars")]
+
+use option_block::{Block128, Block16, Block8};
+use schemars::{schema_for, JsonSchema, SchemaGenerator};
+use serde_json::json;
+
+#[derive(serde::Serialize, JsonSchema)]
+struct PortConfig {
+    baud: u32,
+}
+
+#[test]
+fn dense_array_schema() {
+    let schema = schema_for!(Block16<PortConfig>).to_value();
+    assert_eq!(schema["title"], "Block16_of_PortConfig");
+    assert_eq!(schema["type"], "array");
+    assert_eq!(schema["minItems"], 16);
+    assert_eq!(schema["maxItems"], 16);
+    assert_eq!(schema["items"]["anyOf"][1], json!({ "type": "null" }));
+
+    // The schema must agree with what the `serde` feature actually emits.
+    let block: Block16<PortConfig> = [(3, PortConfig { baud: 9600 })].into_iter().collect();
+    let value = serde_json::to_value(&block).unwrap();
+    let entries = value.as_array().unwrap();
+    assert_eq!(entries.len(), 16);
+    assert_eq!(entries[3], json!({ "baud": 9600 }));
+    assert!(entries.iter().enumerate().all(|(i, entry)| i == 3 || entry.is_null()));
+}
+
+#[test]
+fn instantiations_do_not_collide() {
+    assert_eq!(Block8::<u8>::schema_name(), "Block8_of_uint8");
+    assert_ne!(Block8::<u8>::schema_id(), Block16::<u8>::schema_id());
+    assert_ne!(Block8::<u8>::schema_id(), Block8::<u16>::schema_id());
+
+    let mut generator = SchemaGenerator::default();
+    generator.subschema_for::<Block8<u8>>();
+    generator.subschema_for::<Block128<u8>>();
+    let definitions = generator.definitions();
+    assert_eq!(definitions["Block8_of_uint8"]["maxItems"], 8);
+    assert_eq!(definitions["Block128_of_uint8"]["maxItems"], 128);
+}
@@ -0,0 +1,220 @@
+//! This test module checks the dense `serde` representation of the block variants.
+#![cfg(feature = "serde")]
+
+use option_block::{Block128, Block8};
+use std::rc::Rc;
+
+#[test]
+fn json_round_trips() {
+    let empty = Block8::<u32>::default();
+    let json = serde_json::to_string(&empty).unwrap();
+    assert_eq!(json, "[null,null,null,null,null,null,null,null]");
+    assert_eq!(serde_json::from_str::<Block8<u32>>(&json).unwrap(), empty);
+
+    let sparse: Block8<u32> = [(1, 10), (6, 60)].into_iter().collect();
+    let json = serde_json::to_string(&sparse).unwrap();
+    assert_eq!(json, "[null,10,null,null,null,null,60,null]");
+    assert_eq!(serde_json::from_str::<Block8<u32>>(&json).unwrap(), sparse);
+
+    let full = Block8::from([0, 1, 2, 3, 4, 5, 6, 7]);
+    let json = serde_json::to_string(&full).unwrap();
+    assert_eq!(serde_json::from_str::<Block8<u32>>(&json).unwrap(), full);
+
+    let sparse: Block128<String> = [(0, "first".into()), (127, "last".into())].into_iter().collect();
+    let json = serde_json::to_string(&sparse).unwrap();
+    assert_eq!(serde_json::from_str::<Block128<String>>(&json).unwrap(), sparse);
+
+    let full = Block128::from(core::array::from_fn(|i| i as u8));
+    let json = serde_json::to_string(&full).unwrap();
+    assert_eq!(serde_json::from_str::<Block128<u8>>(&json).unwrap(), full);
+}
+
+#[test]
+fn postcard_round_trips() {
+    let blocks = [
+        Block128::<u64>::default(),
+        [(3, 3), (64, u64::MAX), (100, 0)].into_iter().collect(),
+        Block128::from(core::array::from_fn(|i| i as u64 * 1000)),
+    ];
+    for block in blocks {
+        let bytes = postcard::to_allocvec(&block).unwrap();
+        assert_eq!(postcard::from_bytes::<Block128<u64>>(&bytes).unwrap(), block);
+    }
+
+    let sparse: Block8<String> = [(2, "two".into())].into_iter().collect();
+    let bytes = postcard::to_allocvec(&sparse).unwrap();
+    assert_eq!(postcard::from_bytes::<Block8<String>>(&bytes).unwrap(), sparse);
+}
+
+#[test]
+fn wrong_lengths_are_rejected() {
+    assert!(serde_json::from_str::<Block8<u32>>("[]").is_err());
+    assert!(serde_json::from_str::<Block8<u32>>("[1,2,3,4,5,6,7]").is_err());
+    assert!(serde_json::from_str::<Block8<u32>>("[1,2,3,4,5,6,7,8,9]").is_err());
+    assert!(serde_json::from_str::<Block8<u32>>("[1,2,3,4,5,6,7,8]").is_ok());
+
+    let bytes = postcard::to_allocvec(&Block8::from([1u8; 8])).unwrap();
+    assert!(postcard::from_bytes::<Block128<u8>>(&bytes).is_err());
+}
+
+/// Deserializes into a shared resource so that leaks can be detected.
+struct Tracked(#[allow(dead_code)] Rc<()>);
+
+thread_local! {
+    static RESOURCE: Rc<()> = Rc::new(());
+}
+
+impl<'de> serde::Deserialize<'de> for Tracked {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer)?;
+        Ok(Self(RESOURCE.with(Rc::clone)))
+    }
+}
+
+#[test]
+fn failed_deserialization_drops_elements() {
+    let result = serde_json::from_str::<Block8<Tracked>>(r#"[1,null,2,3,"oops",null,null,null]"#);
+    assert!(result.is_err());
+    assert_eq!(RESOURCE.with(Rc::strong_count), 1);
+
+    let result = serde_json::from_str::<Block8<Tracked>>("[1,2,3]");
+    assert!(result.is_err());
+    assert_eq!(RESOURCE.with(Rc::strong_count), 1);
+
+    let block = serde_json::from_str::<Block8<Tracked>>("[1,2,3,null,null,null,null,null]").unwrap();
+    assert_eq!(RESOURCE.with(Rc::strong_count), 4);
+    drop(block);
+    assert_eq!(RESOURCE.with(Rc::strong_count), 1);
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Sparse {
+    #[serde(with = "option_block::serde_sparse")]
+    small: Block8<u32>,
+    #[serde(with = "option_block::serde_sparse")]
+    large: Block128<String>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct Lenient {
+    #[serde(with = "option_block::serde_sparse::last_wins")]
+    small: Block8<u32>,
+}
+
+#[test]
+fn sparse_round_trips() {
+    let sparse = Sparse {
+        small: [(0, 1), (7, 8)].into_iter().collect(),
+        large: [(2, "two".into()), (127, "last".into())].into_iter().collect(),
+    };
+    let json = serde_json::to_string(&sparse).unwrap();
+    assert_eq!(json, r#"{"small":{"0":1,"7":8},"large":{"2":"two","127":"last"}}"#);
+    assert_eq!(serde_json::from_str::<Sparse>(&json).unwrap(), sparse);
+
+    let bytes = postcard::to_allocvec(&sparse).unwrap();
+    assert!(bytes.len() < 20);
+    assert_eq!(postcard::from_bytes::<Sparse>(&bytes).unwrap(), sparse);
+
+    let empty = Sparse { small: Block8::default(), large: Block128::default() };
+    assert_eq!(serde_json::to_string(&empty).unwrap(), r#"{"small":{},"large":{}}"#);
+    let bytes = postcard::to_allocvec(&empty).unwrap();
+    assert_eq!(postcard::from_bytes::<Sparse>(&bytes).unwrap(), empty);
+}
+
+#[test]
+fn sparse_rejects_invalid_indices() {
+    let out_of_range = r#"{"small":{"8":1},"large":{}}"#;
+    assert!(serde_json::from_str::<Sparse>(out_of_range).is_err());
+    let out_of_range = r#"{"small":{},"large":{"128":"oops"}}"#;
+    assert!(serde_json::from_str::<Sparse>(out_of_range).is_err());
+
+    let duplicates = r#"{"small":{"3":1,"3":2},"large":{}}"#;
+    assert!(serde_json::from_str::<Sparse>(duplicates).is_err());
+    let lenient = serde_json::from_str::<Lenient>(r#"{"small":{"3":1,"3":2}}"#).unwrap();
+    assert_eq!(lenient.small.get(3), Some(&2));
+    assert_eq!(lenient.small.len(), 1);
+}
+
+#[derive(serde::Deserialize)]
+struct TrackedSparse {
+    #[serde(with = "option_block::serde_sparse")]
+    #[allow(dead_code)]
+    block: Block8<Tracked>,
+}
+
+#[test]
+fn failed_sparse_deserialization_drops_elements() {
+    let result = serde_json::from_str::<TrackedSparse>(r#"{"block":{"0":1,"1":2,"9":3}}"#);
+    assert!(result.is_err());
+    let result = serde_json::from_str::<TrackedSparse>(r#"{"block":{"0":1,"1":2,"1":3}}"#);
+    assert!(result.is_err());
+    assert_eq!(RESOURCE.with(Rc::strong_count), 1);
+}
+
+#[test]
+fn deserialize_in_place_overwrites_slots() {
+    use serde::Deserialize;
+    let mut sparse = Block8::<u32>::default();
+    let mut de = serde_json::Deserializer::from_str("[1,null,3,null,null,null,null,8]");
+    Block8::deserialize_in_place(&mut de, &mut sparse).unwrap();
+    assert_eq!(sparse, [(0, 1), (2, 3), (7, 8)].into_iter().collect());
+
+    let mut de = serde_json::Deserializer::from_str("[null,2,null,null,null,null,null,null]");
+    Block8::deserialize_in_place(&mut de, &mut sparse).unwrap();
+    assert_eq!(sparse, [(1, 2)].into_iter().collect());
+
+    let mut de = serde_json::Deserializer::from_str("[null,null,null,null,null,null,null,null]");
+    Block8::deserialize_in_place(&mut de, &mut sparse).unwrap();
+    assert!(sparse.is_empty());
+}
+
+#[test]
+fn failed_deserialize_in_place_stays_consistent() {
+    use serde::Deserialize;
+    let mut block = Block8::<Tracked>::default();
+    let mut de = serde_json::Deserializer::from_str("[1,2,3,4,5,6,7,8]");
+    Block8::deserialize_in_place(&mut de, &mut block).unwrap();
+    assert_eq!(RESOURCE.with(Rc::strong_count), 9);
+
+    // The first three slots are replaced before the failure.
+    let mut de = serde_json::Deserializer::from_str(r#"[null,9,null,"oops",null,null,null,null]"#);
+    assert!(Block8::deserialize_in_place(&mut de, &mut block).is_err());
+    assert!(block.is_vacant(0) && block.is_vacant(2));
+    assert_eq!(block.len(), 6);
+    assert_eq!(RESOURCE.with(Rc::strong_count), 7);
+
+    // A short sequence still overwrites the leading slots before failing.
+    let mut de = serde_json::Deserializer::from_str("[1,2,3]");
+    assert!(Block8::deserialize_in_place(&mut de, &mut block).is_err());
+    assert_eq!(block.len(), 8);
+    assert_eq!(RESOURCE.with(Rc::strong_count), 9);
+
+    drop(block);
+    assert_eq!(RESOURCE.with(Rc::strong_count), 1);
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+fn postcard_max_size_bounds_full_blocks() {
+    use option_block::Block32;
+    use postcard::experimental::max_size::MaxSize;
+
+    #[derive(serde::Serialize, MaxSize)]
+    struct Telemetry {
+        id: u16,
+        reading: i32,
+        ok: bool,
+    }
+
+    let worst = Block32::from(core::array::from_fn(|_| Telemetry { id: u16::MAX, reading: i32::MIN, ok: true }));
+    let bytes = postcard::to_allocvec(&worst).unwrap();
+    assert_eq!(bytes.len(), Block32::<Telemetry>::POSTCARD_MAX_SIZE);
+
+    let small = Block32::from(core::array::from_fn(|i| Telemetry { id: i as u16, reading: 0, ok: false }));
+    assert!(postcard::to_allocvec(&small).unwrap().len() <= Block32::<Telemetry>::POSTCARD_MAX_SIZE);
+
+    let full = Block128::from(core::array::from_fn(|_| u64::MAX));
+    let bytes = postcard::to_allocvec(&full).unwrap();
+    assert_eq!(bytes.len(), Block128::<u64>::POSTCARD_MAX_SIZE);
+    assert_eq!(Block8::<u8>::POSTCARD_MAX_SIZE, 16);
+}
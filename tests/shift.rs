@@ -0,0 +1,38 @@
+//! This test module checks the order-preserving shift operations against an
+//! equivalent `Vec<Option<T>>` model over random sequences of operations.
+
+mod common;
+
+use common::XorShift;
+use option_block::{Block128, Block16, Block32, Block64, Block8};
+
+macro_rules! check_against_model {
+    ($rng:ident $($block:ident)*) => {$(
+        let capacity = $block::<u64>::CAPACITY as usize;
+        let mut block = $block::<u64>::default();
+        let mut model = vec![None; capacity];
+        for _ in 0..1024 {
+            let index = ($rng.next() % capacity as u64) as usize;
+            let val = $rng.next();
+            match val % 4 {
+                0 => {
+                    assert_eq!(block.shift_remove(index), model.remove(index));
+                    model.push(None);
+                }
+                1 => {
+                    model.insert(index, Some(val));
+                    assert_eq!(block.shift_insert(index, val), model.pop().unwrap());
+                }
+                _ => assert_eq!(block.insert(index, val), model[index].replace(val)),
+            }
+
+            assert!((0..capacity).map(|index| block.get(index).copied()).eq(model.iter().copied()));
+        }
+    )*};
+}
+
+#[test]
+fn shifts_match_vec_model() {
+    let mut rng = XorShift(0x853c_49e6_748f_ea9b);
+    check_against_model!(rng Block8 Block16 Block32 Block64 Block128);
+}
@@ -0,0 +1,23 @@
+//! This test module checks the `ufmt` output of the block variants.
+#![cfg(feature = "ufmt")]
+
+use heapless::String;
+use option_block::{Block128, Block8};
+use ufmt::uwrite;
+
+#[test]
+fn sparse_block_as_map() {
+    let block: Block8<u32> = [(0, 12), (5, 99)].into_iter().collect();
+    let mut out = String::<32>::new();
+    uwrite!(out, "{:?}", block).unwrap();
+    assert_eq!(out, "{0: 12, 5: 99}");
+
+    let mut out = String::<32>::new();
+    uwrite!(out, "{:?}", Block128::<u8>::default()).unwrap();
+    assert_eq!(out, "{}");
+
+    let nested: Block8<Block8<i8>> = [(7, [(1, -1)].into_iter().collect())].into_iter().collect();
+    let mut out = String::<32>::new();
+    uwrite!(out, "{:?}", nested).unwrap();
+    assert_eq!(out, "{7: {1: -1}}");
+}
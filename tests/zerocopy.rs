@@ -0,0 +1,65 @@
+//! This test module checks the `zerocopy` byte conversions of the block variants.
+#![cfg(feature = "zerocopy")]
+
+use option_block::{Block128, Block16, Block8};
+use std::mem::size_of;
+use zerocopy::{FromBytes, Immutable, IntoBytes, Unaligned};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, Unaligned)]
+#[repr(C)]
+struct Record {
+    id: [u8; 2],
+    flags: u8,
+}
+
+#[test]
+fn packet_round_trips() {
+    let block: Block16<Record> =
+        [(1, Record { id: [1, 2], flags: 3 }), (14, Record { id: [0xff; 2], flags: 0 })].into_iter().collect();
+
+    let mut packet = [0xee; 2 + 16 * 3];
+    block.write_to_bytes(&mut packet).unwrap();
+    assert_eq!(packet[..2], [0b10, 0b0100_0000]);
+    assert_eq!(packet[2..5], [0; 3]);
+    assert_eq!(packet[5..8], [1, 2, 3]);
+    assert_eq!(Block16::<Record>::read_from_bytes(&packet), Some(block));
+
+    // The output is deterministic regardless of the buffer's previous contents.
+    let mut other = [0x11; 2 + 16 * 3];
+    Block16::<Record>::read_from_bytes(&packet).unwrap().write_to_bytes(&mut other).unwrap();
+    assert_eq!(packet, other);
+
+    assert!(Block16::<Record>::read_from_bytes(&packet[1..]).is_none());
+    assert!(Block8::<u64>::default().write_to_bytes(&mut [0; 8 * 8]).is_none());
+}
+
+#[test]
+fn corrupted_masks_only_read_given_bytes() {
+    let mut packet = [0; 16 + 128 * 4];
+    Block128::<u32>::default().write_to_bytes(&mut packet).unwrap();
+    assert!(packet.iter().all(|&byte| byte == 0));
+
+    // Claim that every slot is occupied even though nothing was ever written there.
+    packet[..16].fill(0xff);
+    packet[16 + 4 * 127..].copy_from_slice(&7u32.to_le_bytes());
+    let block = Block128::<u32>::read_from_bytes(&packet).unwrap();
+    assert!(block.is_full());
+    assert_eq!(block.get(127), Some(&7));
+    assert_eq!(block.iter().filter(|&&val| val == 0).count(), 127);
+}
+
+#[test]
+fn aligned_slots_are_packed() {
+    // The slots immediately follow the mask, even though `u64` is 8-byte aligned.
+    assert!(size_of::<Block8<u64>>() > 1 + 8 * 8);
+    let block: Block8<u64> = [(0, u64::MAX), (7, 0x0102)].into_iter().collect();
+    assert!(block.write_to_bytes(&mut [0; 72]).is_none());
+
+    let mut packet = [0xee; 1 + 8 * 8];
+    block.write_to_bytes(&mut packet).unwrap();
+    assert_eq!(packet[0], 0b1000_0001);
+    assert_eq!(packet[1..9], [0xff; 8]);
+    assert_eq!(packet[57..], [2, 1, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(Block8::<u64>::read_from_bytes(&packet), Some(block));
+    assert!(Block8::<u64>::read_from_bytes(&[0; 72]).is_none());
+}